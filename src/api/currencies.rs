@@ -1,6 +1,9 @@
 use serde::{Deserialize, Serialize};
 
-use crate::currency::Currency;
+use crate::{
+    cache::{file_cache::read_and_invalid_cache_file, CacheConfigs},
+    currency::Currency,
+};
 
 use super::ApiEndpoints;
 
@@ -14,6 +17,12 @@ pub async fn get_supported_currencies_from_api() -> Result<Vec<Currency>, String
     }
 }
 
+pub fn get_cached_supported_currencies() -> Option<Vec<Currency>> {
+    read_and_invalid_cache_file::<ApiResponse>(CacheConfigs::Currencies, None)
+        .ok()
+        .map(|response| response.supported_codes)
+}
+
 #[derive(Deserialize, Serialize, Debug, Clone)]
 struct ApiResponse {
     result: String,
@@ -1,27 +1,71 @@
 use std::collections::HashMap;
 
+use chrono::{DateTime, NaiveDate, Utc};
+use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
 
 use crate::{currency::Currency, error::print_info};
 
 use super::ApiEndpoints;
 
-pub async fn get_exchange_rates(source: Currency) -> Result<HashMap<String, f64>, String> {
-    print_info(&format!(
-        "Getting exchange rates for currency {}",
-        source.get_code(),
-    ));
+pub struct ExchangeRates {
+    pub rates: HashMap<String, Decimal>,
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+pub async fn get_exchange_rates(source: Currency) -> Result<HashMap<String, Decimal>, String> {
+    get_exchange_rates_at(source, None)
+        .await
+        .map(|exchange_rates| exchange_rates.rates)
+}
+
+// without a date the latest rates are returned
+pub async fn get_exchange_rates_at(
+    source: Currency,
+    date: Option<NaiveDate>,
+) -> Result<ExchangeRates, String> {
+    let endpoint = match date {
+        Some(date) => {
+            print_info(&format!(
+                "Getting exchange rates for currency {} on {}",
+                source.get_code(),
+                date
+            ));
+            ApiEndpoints::HistoricalExchangeRate(date)
+        }
+        None => {
+            print_info(&format!(
+                "Getting exchange rates for currency {}",
+                source.get_code(),
+            ));
+            ApiEndpoints::ExchangeRate
+        }
+    };
 
-    let endpoint = ApiEndpoints::ExchangeRate;
     let response: Result<CurrencyRatesApiResponse, String> = endpoint.request(Some(source)).await;
 
     match response {
-        Ok(response) => Ok(response.conversion_rates),
+        Ok(response) => {
+            let updated_at = match date {
+                Some(date) => date.and_hms_opt(0, 0, 0).map(|date| date.and_utc()),
+                None => response
+                    .time_last_update_utc
+                    .and_then(|time| DateTime::parse_from_rfc2822(&time).ok())
+                    .map(|time| time.with_timezone(&Utc)),
+            };
+
+            Ok(ExchangeRates {
+                rates: response.conversion_rates,
+                updated_at,
+            })
+        }
         Err(e) => Err(e.to_string()),
     }
 }
 
 #[derive(Deserialize, Serialize, Debug, Clone)]
 struct CurrencyRatesApiResponse {
-    conversion_rates: HashMap<String, f64>,
+    conversion_rates: HashMap<String, Decimal>,
+    #[serde(default)]
+    time_last_update_utc: Option<String>,
 }
@@ -1,19 +1,43 @@
-use std::{fmt, thread};
+use std::{
+    fmt,
+    sync::atomic::{AtomicBool, Ordering},
+    thread,
+};
+
+use chrono::NaiveDate;
 
 use crate::{
     cache::{
         file_cache::{create_cache_file, read_and_invalid_cache_file},
         CacheConfigs,
     },
-    currency::Currency,
+    currency::{get_iso_currencies, Currency},
+    error::print_verbose,
+    validation::suggestions::{suggest_currencies, with_suggestions},
 };
 
+use self::currencies::get_cached_supported_currencies;
+
 pub mod currencies;
 pub mod exchange_rate;
 
+pub const PROVIDER: &str = "exchangerate-api.com";
+
+static OFFLINE: AtomicBool = AtomicBool::new(false);
+
+// offline, only cached responses are used
+pub fn set_offline(offline: bool) {
+    OFFLINE.store(offline, Ordering::Relaxed);
+}
+
+pub fn is_offline() -> bool {
+    OFFLINE.load(Ordering::Relaxed)
+}
+
 pub enum ApiEndpoints {
     SupportedCurrencies,
     ExchangeRate,
+    HistoricalExchangeRate(NaiveDate),
 }
 
 impl ApiEndpoints {
@@ -28,6 +52,9 @@ impl ApiEndpoints {
             ApiEndpoints::ExchangeRate => {
                 format!("https://v6.exchangerate-api.com/v6/{}/latest/", api_key)
             }
+            ApiEndpoints::HistoricalExchangeRate(_) => {
+                format!("https://v6.exchangerate-api.com/v6/{}/history", api_key)
+            }
         }
     }
 
@@ -35,14 +62,38 @@ impl ApiEndpoints {
         match self {
             ApiEndpoints::SupportedCurrencies => CacheConfigs::Currencies,
             ApiEndpoints::ExchangeRate => CacheConfigs::ExchangeRates,
+            ApiEndpoints::HistoricalExchangeRate(date) => {
+                CacheConfigs::HistoricalExchangeRates(*date)
+            }
+        }
+    }
+
+    // what is requested, without the API key of the URL
+    fn describe(&self, currency: Option<&Currency>) -> String {
+        let description = match self {
+            ApiEndpoints::SupportedCurrencies => "supported currencies".to_string(),
+            ApiEndpoints::ExchangeRate => "exchange rates".to_string(),
+            ApiEndpoints::HistoricalExchangeRate(date) => format!("exchange rates of {}", date),
+        };
+
+        match currency {
+            Some(currency) => format!("{} for {}", description, currency.get_code()),
+            None => description,
         }
     }
 
     pub fn get_url(&self, currency: Option<Currency>) -> String {
         let url = self.prepare_url();
-        match currency {
+        let url = match currency {
             Some(currency) => format!("{}/{}", url, currency.get_code()),
             None => url,
+        };
+
+        match self {
+            ApiEndpoints::HistoricalExchangeRate(date) => {
+                format!("{}/{}", url, date.format("%Y/%m/%d"))
+            }
+            _ => url,
         }
     }
 
@@ -55,10 +106,19 @@ impl ApiEndpoints {
         let cache_config = self.get_cache_config();
 
         let cached_response = read_and_invalid_cache_file(cache_config.clone(), currency.clone());
+        let description = self.describe(currency.as_ref());
 
         match cached_response {
-            Ok(cached_response) => Ok(cached_response),
+            Ok(cached_response) => {
+                print_verbose(&format!("Using cached {}", description));
+                Ok(cached_response)
+            }
+            Err(_) if is_offline() => Err(format!(
+                "No cached {} available offline, run without --offline to fetch them",
+                description
+            )),
             Err(_) => {
+                print_verbose(&format!("Requesting {} from {}", description, PROVIDER));
                 let response = reqwest::get(&self.get_url(currency.clone())).await;
 
                 let response = match response {
@@ -68,7 +128,7 @@ impl ApiEndpoints {
 
                             match response {
                                 Ok(response) => {
-                                    return Err(response.to_string());
+                                    return Err(response.describe(currency.as_ref()));
                                 }
                                 Err(e) => {
                                     return Err(e.to_string());
@@ -110,6 +170,27 @@ pub struct ErrorResponse {
     error_type: String,
 }
 
+impl ErrorResponse {
+    pub fn describe(&self, currency: Option<&Currency>) -> String {
+        let code = match (self.error_type.as_str(), currency) {
+            ("unsupported-code", Some(currency)) => currency.get_code(),
+            _ => return self.to_string(),
+        };
+
+        // the provider's list is rarely cached when it rejects a code, the ISO list is used instead
+        let candidates: Vec<Currency> = get_cached_supported_currencies()
+            .unwrap_or_else(get_iso_currencies)
+            .into_iter()
+            .filter(|candidate| candidate.get_code() != code)
+            .collect();
+
+        with_suggestions(
+            format!("{} {}", self, code),
+            &suggest_currencies(code, &candidates),
+        )
+    }
+}
+
 impl fmt::Display for ErrorResponse {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let message = match self.error_type.as_str() {
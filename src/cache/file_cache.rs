@@ -1,13 +1,13 @@
 use chrono::{DateTime, Utc};
 
-use serde::Serialize;
+use serde::{de::DeserializeOwned, Serialize};
 use serde_json::to_string_pretty;
-use std::io::{self, Result};
+use std::io::{self, Result, Write};
 use std::{fs, path::Path};
 
 use crate::currency::Currency;
 
-use super::{CacheConfigs, CACHE_DIR};
+use super::{CacheConfig, CacheConfigs, CACHE_DIR, PRESERVED_CACHE_DIRS};
 
 pub fn create_cache_file<T: Serialize>(
     serializable: &T,
@@ -121,18 +121,125 @@ pub fn read_and_invalid_cache_file<T: for<'de> serde::Deserialize<'de>>(
     ))
 }
 
-pub async fn rest_cache() -> Result<()> {
-    let cache_folder = fs::read_dir(CACHE_DIR);
+fn get_log_path(config: &CacheConfig) -> String {
+    format!("{}/{}.jsonl", config.get_path(), config.get_file_name())
+}
+
+// logs keep one JSON entry per line, so adding an entry does not rewrite the file
+pub fn append_log_entry<T: Serialize>(entry: &T, cache_config: CacheConfigs) -> Result<()> {
+    let config = cache_config.get_config(None);
+    if !config.is_cache_enabled() {
+        return Err(io::Error::new(io::ErrorKind::Other, "Cache is not enabled"));
+    }
+
+    fs::create_dir_all(config.get_path())?;
+
+    let mut file = fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(get_log_path(&config))?;
+
+    writeln!(file, "{}", serde_json::to_string(entry)?)
+}
+
+// lines that can't be read, like a line cut off by a crash, are skipped
+pub fn read_log_entries<T: DeserializeOwned>(cache_config: CacheConfigs) -> Result<Vec<T>> {
+    let config = cache_config.get_config(None);
+    if !config.is_cache_enabled() {
+        return Err(io::Error::new(io::ErrorKind::Other, "Cache is not enabled"));
+    }
+
+    let contents = fs::read_to_string(get_log_path(&config))?;
+
+    Ok(contents
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .filter_map(|line| serde_json::from_str(line).ok())
+        .collect())
+}
+
+pub fn write_log_entries<T: Serialize>(entries: &[T], cache_config: CacheConfigs) -> Result<()> {
+    let config = cache_config.get_config(None);
+    if !config.is_cache_enabled() {
+        return Err(io::Error::new(io::ErrorKind::Other, "Cache is not enabled"));
+    }
 
-    match cache_folder {
-        Ok(_) => {
-            let remove = fs::remove_dir_all(CACHE_DIR);
+    fs::create_dir_all(config.get_path())?;
 
-            match remove {
-                Ok(_) => Ok(()),
-                Err(e) => Err(e),
-            }
+    let mut contents = String::new();
+    for entry in entries {
+        contents.push_str(&serde_json::to_string(entry)?);
+        contents.push('\n');
+    }
+
+    // the log is replaced in one step, a crash leaves either the old or the new log
+    let path = get_log_path(&config);
+    let temporary_path = format!("{}.tmp", path);
+    fs::write(&temporary_path, contents)?;
+    fs::rename(temporary_path, path)
+}
+
+pub fn log_exists(cache_config: CacheConfigs) -> bool {
+    Path::new(&get_log_path(&cache_config.get_config(None))).exists()
+}
+
+fn get_last_id_path(config: &CacheConfig) -> String {
+    format!("{}/{}.last_id", config.get_path(), config.get_file_name())
+}
+
+// the last id given to a log entry is kept next to the log, so appending does not read it
+pub fn read_last_log_id(cache_config: CacheConfigs) -> Option<usize> {
+    fs::read_to_string(get_last_id_path(&cache_config.get_config(None)))
+        .ok()
+        .and_then(|contents| contents.trim().parse::<usize>().ok())
+}
+
+pub fn write_last_log_id(id: usize, cache_config: CacheConfigs) -> Result<()> {
+    let config = cache_config.get_config(None);
+    if !config.is_cache_enabled() {
+        return Err(io::Error::new(io::ErrorKind::Other, "Cache is not enabled"));
+    }
+
+    fs::create_dir_all(config.get_path())?;
+    fs::write(get_last_id_path(&config), id.to_string())
+}
+
+// files written by create_cache_file before the data moved to a log
+pub fn read_legacy_cache_file<T: DeserializeOwned>(cache_config: CacheConfigs) -> Option<T> {
+    let config = cache_config.get_config(None);
+    let path = format!("{}/{}.json", config.get_path(), config.get_file_name());
+
+    fs::read_to_string(path)
+        .ok()
+        .and_then(|contents| serde_json::from_str(&contents).ok())
+}
+
+pub fn remove_legacy_cache_file(cache_config: CacheConfigs) -> Result<()> {
+    let config = cache_config.get_config(None);
+
+    fs::remove_file(format!(
+        "{}/{}.json",
+        config.get_path(),
+        config.get_file_name()
+    ))
+}
+
+pub async fn rest_cache() -> Result<()> {
+    let cache_folder = fs::read_dir(CACHE_DIR)?;
+
+    // the command history, past rates and rate snapshots survive resetting the cache
+    for entry in cache_folder {
+        let entry = entry?;
+        let preserved = PRESERVED_CACHE_DIRS
+            .iter()
+            .any(|dir| entry.file_name() == *dir);
+
+        match (preserved, entry.path().is_dir()) {
+            (true, _) => (),
+            (false, true) => fs::remove_dir_all(entry.path())?,
+            (false, false) => fs::remove_file(entry.path())?,
         }
-        Err(e) => Err(e),
     }
+
+    Ok(())
 }
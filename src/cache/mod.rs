@@ -1,14 +1,31 @@
 use crate::currency::Currency;
-use chrono::Utc;
+use chrono::{NaiveDate, Utc};
 
 pub mod file_cache;
 
 pub const CACHE_DIR: &str = "cache";
+// rates of a past day do not change
+const HISTORICAL_RATES_CACHE_TIME_IN_HOURS: i32 = 24 * 365;
+// snapshots are only replaced when the rates change
+const RATE_SNAPSHOTS_CACHE_TIME_IN_HOURS: i32 = 24 * 365;
+// the lifetime of a single history entry
+const COMMAND_HISTORY_CACHE_TIME_IN_HOURS: i32 = 24 * 365;
+const COMMAND_HISTORY_DIR: &str = "command_history";
+const HISTORICAL_RATES_DIR: &str = "historical_exchange_rates";
+const RATE_SNAPSHOTS_DIR: &str = "rate_snapshots";
+// kept when the cache is updated, snapshots are the baseline of matrix --change
+pub const PRESERVED_CACHE_DIRS: [&str; 3] = [
+    COMMAND_HISTORY_DIR,
+    HISTORICAL_RATES_DIR,
+    RATE_SNAPSHOTS_DIR,
+];
 
 #[derive(Clone)]
 pub enum CacheConfigs {
     Currencies,
     ExchangeRates,
+    HistoricalExchangeRates(NaiveDate),
+    RateSnapshots,
     CommandHistory,
 }
 
@@ -45,14 +62,28 @@ impl CacheConfigs {
                     now.format("%Y-%m-%d_%H-%M-%S").to_string(),
                 )
             }
+            CacheConfigs::HistoricalExchangeRates(date) => CacheConfig::new(
+                HISTORICAL_RATES_CACHE_TIME_IN_HOURS,
+                format!("{}/{}", HISTORICAL_RATES_DIR, date.format("%Y-%m-%d")),
+                currency,
+                Utc::now().format("%Y-%m-%d_%H-%M-%S").to_string(),
+            ),
+            CacheConfigs::RateSnapshots => CacheConfig::new(
+                RATE_SNAPSHOTS_CACHE_TIME_IN_HOURS,
+                RATE_SNAPSHOTS_DIR.to_string(),
+                currency,
+                "snapshots".to_string(),
+            ),
             CacheConfigs::CommandHistory => {
                 let lifetime_in_hours = match std::env::var("COMMAND_HISTORY_CACHE_TIME_IN_HOURS") {
-                    Ok(val) => val.parse::<i32>().unwrap_or(1),
-                    Err(_) => 1,
+                    Ok(val) => val
+                        .parse::<i32>()
+                        .unwrap_or(COMMAND_HISTORY_CACHE_TIME_IN_HOURS),
+                    Err(_) => COMMAND_HISTORY_CACHE_TIME_IN_HOURS,
                 };
                 CacheConfig::new(
                     lifetime_in_hours,
-                    "command_history".to_string(),
+                    COMMAND_HISTORY_DIR.to_string(),
                     currency,
                     "commands".to_string(),
                 )
@@ -96,6 +127,10 @@ impl CacheConfig {
         self.file_name.clone()
     }
 
+    pub fn get_lifetime_in_hours(&self) -> i32 {
+        self.lifetime_in_hours
+    }
+
     pub fn is_cache_enabled(&self) -> bool {
         self.lifetime_in_hours > 0
     }
@@ -0,0 +1,318 @@
+use std::{
+    collections::HashMap,
+    fs,
+    io::{self, Read, Write},
+};
+
+use chrono::NaiveDate;
+use futures::future::join_all;
+use serde::Serialize;
+
+use crate::{
+    conversion::converter::Converter,
+    currency::{
+        get_rate_table_at, get_validation_currencies, history::CommandHistory, Currency, RateTable,
+    },
+    error::print_info,
+    validation::{amount::AmountParser, validate, ValidationType},
+};
+
+use super::options::{ConversionOptions, OutputFormat};
+
+pub const BATCH_HEADERS: [&str; 8] = [
+    "source",
+    "target",
+    "amount",
+    "date",
+    "rate",
+    "result",
+    "rate_timestamp",
+    "error",
+];
+
+// rate tables are fetched once per source currency and date
+pub type RateTableKey = (String, Option<NaiveDate>);
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct BatchRow {
+    pub line: u64,
+    pub columns: Vec<String>,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct BatchResult {
+    pub line: u64,
+    pub source: String,
+    pub target: String,
+    pub amount: String,
+    pub date: Option<String>,
+    pub rate: Option<String>,
+    pub result: Option<String>,
+    pub rate_timestamp: Option<String>,
+    pub error: Option<String>,
+}
+
+impl BatchRow {
+    pub fn get_column(&self, index: usize) -> &str {
+        self.columns
+            .get(index)
+            .map(|column| column.trim())
+            .unwrap_or("")
+    }
+
+    pub fn get_source(&self) -> String {
+        self.get_column(0).to_uppercase()
+    }
+
+    pub fn get_target(&self) -> String {
+        self.get_column(1).to_uppercase()
+    }
+
+    pub fn get_amount(&self) -> &str {
+        self.get_column(2)
+    }
+
+    pub fn get_date(&self) -> Option<&str> {
+        match self.get_column(3) {
+            "" => None,
+            date => Some(date),
+        }
+    }
+
+    pub fn parse_date(&self) -> Result<Option<NaiveDate>, String> {
+        match self.get_date() {
+            Some(date) => NaiveDate::parse_from_str(date, "%Y-%m-%d")
+                .map(Some)
+                .map_err(|_| format!("{} is not a valid date, use YYYY-MM-DD", date)),
+            None => Ok(None),
+        }
+    }
+
+    pub fn get_rate_table_key(&self) -> Result<RateTableKey, String> {
+        if let Some(error) = &self.error {
+            return Err(error.clone());
+        }
+
+        if !(3..=4).contains(&self.columns.len()) {
+            return Err(format!(
+                "Expected source, target, amount and an optional date, found {} columns",
+                self.columns.len()
+            ));
+        }
+
+        Ok((self.get_source(), self.parse_date()?))
+    }
+}
+
+pub fn read_batch_rows<R: Read>(reader: R) -> Vec<BatchRow> {
+    read_csv_rows(reader, "source")
+}
+
+// a header row is recognised by its first column and skipped
+pub fn read_csv_rows<R: Read>(reader: R, first_header: &str) -> Vec<BatchRow> {
+    let mut reader = csv::ReaderBuilder::new()
+        .has_headers(false)
+        .flexible(true)
+        .from_reader(reader);
+
+    let mut rows: Vec<BatchRow> = vec![];
+
+    for (index, record) in reader.records().enumerate() {
+        let row = match record {
+            Ok(record) => BatchRow {
+                line: record.position().map_or(0, |position| position.line()),
+                columns: record.iter().map(|column| column.to_string()).collect(),
+                error: None,
+            },
+            Err(e) => BatchRow {
+                line: e.position().map_or(0, |position| position.line()),
+                columns: vec![],
+                error: Some(format!("Could not read the row: {}", e)),
+            },
+        };
+
+        if index == 0 && row.get_column(0).eq_ignore_ascii_case(first_header) {
+            continue;
+        }
+
+        rows.push(row);
+    }
+
+    rows
+}
+
+// unknown currency codes fail their row before any rate table is fetched
+pub async fn validate_batch_rows(rows: &mut [BatchRow], supported_currencies: Vec<Currency>) {
+    for row in rows.iter_mut() {
+        if row.get_rate_table_key().is_err() {
+            continue;
+        }
+
+        for code in [row.get_source(), row.get_target()] {
+            if let Err(e) =
+                validate(&code, ValidationType::Currency, Some(&supported_currencies)).await
+            {
+                row.error = Some(e);
+                break;
+            }
+        }
+    }
+}
+
+pub fn convert_batch_rows(
+    rows: &[BatchRow],
+    rate_tables: &HashMap<RateTableKey, Result<RateTable, String>>,
+    options: &ConversionOptions,
+) -> Vec<BatchResult> {
+    let rounding = options.get_rounding();
+    let fees = options.get_fee_schedule();
+    let parser = AmountParser::from_env();
+
+    rows.iter()
+        .map(|row| {
+            let mut result = BatchResult {
+                line: row.line,
+                source: row.get_source(),
+                target: row.get_target(),
+                amount: row.get_amount().to_string(),
+                date: row.get_date().map(|date| date.to_string()),
+                rate: None,
+                result: None,
+                rate_timestamp: None,
+                error: None,
+            };
+
+            let conversion = row.get_rate_table_key().and_then(|key| {
+                let rate_table = match rate_tables.get(&key) {
+                    Some(rate_table) => rate_table.as_ref().map_err(|e| e.clone())?,
+                    None => return Err(format!("Exchange rates for {} not found", key.0)),
+                };
+                let amount = parser
+                    .clone()
+                    .for_currency(rate_table.get_base())
+                    .parse(row.get_amount())?;
+                let rate = rate_table.get_rate(&row.get_target())?;
+
+                let converter = Converter::new(
+                    rate_table.get_base().clone(),
+                    Currency::new_from_code(row.get_target()),
+                    rate,
+                )
+                .with_fees(fees.clone())
+                .with_rounding(rounding);
+
+                let breakdown = converter.convert(amount)?;
+
+                Ok((rate_table, converter, breakdown))
+            });
+
+            match conversion {
+                Ok((rate_table, converter, breakdown)) => {
+                    result.rate = Some(breakdown.effective_rate.normalize().to_string());
+                    result.result =
+                        Some(converter.get_target().format_amount(breakdown.net_result));
+                    result.rate_timestamp = rate_table
+                        .get_updated_at()
+                        .map(|updated_at| updated_at.to_rfc3339());
+                }
+                Err(e) => result.error = Some(e),
+            }
+
+            result
+        })
+        .collect()
+}
+
+pub fn write_batch_csv<W: Write>(results: &[BatchResult], writer: W) -> Result<(), String> {
+    let mut writer = csv::Writer::from_writer(writer);
+
+    writer
+        .write_record(BATCH_HEADERS)
+        .map_err(|e| e.to_string())?;
+
+    for result in results {
+        writer
+            .write_record([
+                result.source.as_str(),
+                result.target.as_str(),
+                result.amount.as_str(),
+                result.date.as_deref().unwrap_or(""),
+                result.rate.as_deref().unwrap_or(""),
+                result.result.as_deref().unwrap_or(""),
+                result.rate_timestamp.as_deref().unwrap_or(""),
+                result.error.as_deref().unwrap_or(""),
+            ])
+            .map_err(|e| e.to_string())?;
+    }
+
+    writer.flush().map_err(|e| e.to_string())
+}
+
+// no input or - reads from stdin
+pub fn open_input(input: Option<&str>) -> Result<Box<dyn Read>, String> {
+    match input {
+        None | Some("-") => Ok(Box::new(io::stdin())),
+        Some(path) => match fs::File::open(path) {
+            Ok(file) => Ok(Box::new(file)),
+            Err(e) => Err(format!("Could not read {}: {}", path, e)),
+        },
+    }
+}
+
+pub async fn display_batch(
+    input: &Option<String>,
+    format: OutputFormat,
+    options: &ConversionOptions,
+) -> Result<CommandHistory, String> {
+    let reader = open_input(input.as_deref())?;
+
+    let mut rows = read_batch_rows(reader);
+
+    let codes: Vec<String> = rows
+        .iter()
+        .flat_map(|row| [row.get_source(), row.get_target()])
+        .collect();
+    validate_batch_rows(&mut rows, get_validation_currencies(&codes).await).await;
+
+    let mut keys: Vec<RateTableKey> = vec![];
+    for key in rows.iter().filter_map(|row| row.get_rate_table_key().ok()) {
+        if !keys.contains(&key) {
+            keys.push(key);
+        }
+    }
+
+    let get_rate_tables = keys.into_iter().map(|key| async move {
+        let rate_table = get_rate_table_at(&key.0, key.1).await;
+        (key, rate_table)
+    });
+    let rate_tables: HashMap<RateTableKey, Result<RateTable, String>> =
+        join_all(get_rate_tables).await.into_iter().collect();
+
+    let results = convert_batch_rows(&rows, &rate_tables, options);
+
+    match format {
+        OutputFormat::Json => serde_json::to_string_pretty(&results)
+            .map(|json| println!("{}", json))
+            .map_err(|e| e.to_string())?,
+        _ => write_batch_csv(&results, io::stdout())?,
+    };
+
+    let failed = results
+        .iter()
+        .filter(|result| result.error.is_some())
+        .count();
+    let summary = format!(
+        "Converted {} of {} rows, {} failed",
+        results.len() - failed,
+        results.len(),
+        failed
+    );
+    print_info(&summary);
+
+    let command = match input {
+        Some(input) => format!("batch {}", input),
+        None => "batch".to_string(),
+    };
+    Ok(CommandHistory::new(command, Some(options.get_rounding())).with_summary(summary))
+}
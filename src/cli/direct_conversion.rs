@@ -1,66 +1,288 @@
 use crate::{
-    conversion::convert,
-    currency::{get_rate, get_supported_currencies, history::CommandHistory},
-    error::print_error,
-    validation::{validate, ValidationType},
+    conversion::converter::{ConversionBreakdown, Converter, FeeCurrency},
+    currency::{
+        get_rate_table, get_validation_currencies,
+        history::{CommandHistory, ConversionRecord},
+        Currency,
+    },
+    validation::{amount::AmountParser, validate, ValidationType},
 };
 use colored::Colorize;
 use futures::future::join_all;
+use rust_decimal::Decimal;
 
-pub async fn display_direct_conversion(source: &String, target: &String, amount: &str) {
-    let rate = get_rate(source, target).await;
-    let amount = amount.parse::<f64>().unwrap();
+use super::{options::ConversionOptions, table::Table};
 
-    match rate {
-        Ok(rate) => {
-            let conversion_results = convert(amount, rate);
+type TargetConversion = (String, Result<(Converter, ConversionBreakdown), String>);
 
-            let output = format!(
-                "{} {} = {} {}  // exchange rate: {}",
-                amount.to_string().bold(),
-                source.to_uppercase(),
-                conversion_results.to_string().green(),
-                target.to_uppercase(),
-                rate.to_string().yellow()
-            );
+pub fn get_target_codes(target: &str) -> Vec<String> {
+    target
+        .split(',')
+        .map(|code| code.trim().to_uppercase())
+        .filter(|code| !code.is_empty())
+        .collect()
+}
+
+pub async fn display_direct_conversion(
+    source: &String,
+    target: &String,
+    amount: &str,
+    options: &ConversionOptions,
+) -> Result<CommandHistory, String> {
+    let rate_table = get_rate_table(source).await;
+    let amount = AmountParser::from_env().parse(amount)?;
+    let rate_table = rate_table?;
+
+    let rounding = options.get_rounding();
+    let fees = options.get_fee_schedule();
+
+    let conversions: Vec<TargetConversion> = get_target_codes(target)
+        .into_iter()
+        .map(|target| {
+            let conversion = rate_table.get_rate(&target).and_then(|rate| {
+                let converter = Converter::new(
+                    rate_table.get_base().clone(),
+                    Currency::new_from_code(target.clone()),
+                    rate,
+                )
+                .with_fees(fees.clone())
+                .with_rounding(rounding);
 
-            println!("{}", output);
+                let breakdown = match options.reverse {
+                    true => converter.convert_reverse(amount),
+                    false => converter.convert(amount),
+                };
 
-            let command = format!("{} {} {}", source, target, amount);
-            let _ = CommandHistory::save(output, command);
+                breakdown.map(|breakdown| (converter, breakdown))
+            });
+
+            (target, conversion)
+        })
+        .collect();
+
+    match conversions.as_slice() {
+        [(_, Ok((converter, breakdown)))] => {
+            display_single_conversion(converter, breakdown, options)
         }
-        Err(e) => {
-            print_error(e.to_string().as_str());
+        [(_, Err(e))] => return Err(e.clone()),
+        // nothing to show or to store in the history when every target failed
+        _ if conversions
+            .iter()
+            .all(|(_, conversion)| conversion.is_err()) =>
+        {
+            let errors: Vec<String> = conversions
+                .iter()
+                .filter_map(|(_, conversion)| conversion.as_ref().err().cloned())
+                .collect();
+            return Err(errors.join(", "));
         }
+        _ => display_multi_target_conversion(rate_table.get_base(), amount, &conversions, options),
+    };
+
+    let records = conversions
+        .iter()
+        .filter_map(|(_, conversion)| conversion.as_ref().ok())
+        .map(|(converter, breakdown)| ConversionRecord {
+            source: converter.get_source().get_code().clone(),
+            target: converter.get_target().get_code().clone(),
+            amount: breakdown.amount,
+            rate: breakdown.mid_market_rate,
+            result: breakdown.net_result,
+            reverse: options.reverse,
+            fees: converter.get_fees().clone(),
+        })
+        .collect();
+
+    let command = match options.reverse {
+        true => format!("{} {} --receive {}", source, target, amount),
+        false => format!("{} {} {}", source, target, amount),
+    };
+    Ok(CommandHistory::new(command, Some(rounding))
+        .with_conversions(records)
+        .with_rates(&rate_table))
+}
+
+fn display_single_conversion(
+    converter: &Converter,
+    breakdown: &ConversionBreakdown,
+    options: &ConversionOptions,
+) {
+    let source = converter.get_source();
+    let target = converter.get_target();
+
+    let output = match options.reverse {
+        true => format!(
+            "{} {} needed to receive {} {}  // exchange rate: {}",
+            source.format_amount(breakdown.amount).green(),
+            source.get_code(),
+            target.format_amount(breakdown.net_result).bold(),
+            target.get_code(),
+            target.format_rate(breakdown.mid_market_rate).yellow()
+        ),
+        false => format!(
+            "{} {} = {} {}  // exchange rate: {}",
+            breakdown.amount.to_string().bold(),
+            source.get_code(),
+            target.format_amount(breakdown.net_result).green(),
+            target.get_code(),
+            target.format_rate(breakdown.mid_market_rate).yellow()
+        ),
+    };
+
+    println!("{}", output);
+
+    if !converter.get_fees().is_empty() {
+        print_breakdown(converter, breakdown);
+    }
+}
+
+fn display_multi_target_conversion(
+    source: &Currency,
+    amount: Decimal,
+    conversions: &[TargetConversion],
+    options: &ConversionOptions,
+) {
+    let with_fees = conversions.iter().any(|(_, conversion)| {
+        conversion
+            .as_ref()
+            .is_ok_and(|(converter, _)| !converter.get_fees().is_empty())
+    });
+
+    let needed_header = format!("{} needed", source.get_code());
+    let headers = match (options.reverse, with_fees) {
+        (false, false) => vec!["Target", "Amount", "Exchange rate"],
+        (false, true) => vec!["Target", "Mid-market", "Fees", "Net", "Effective rate"],
+        (true, _) => vec!["Target", "Receive", needed_header.as_str(), "Exchange rate"],
+    };
+
+    let mut table = Table::new(headers);
+
+    for (target_code, conversion) in conversions {
+        let (converter, breakdown) = match conversion {
+            Ok(conversion) => conversion,
+            Err(e) => {
+                table.add_row(vec![target_code.clone(), e.clone()]);
+                continue;
+            }
+        };
+        let target = converter.get_target();
+
+        let row = match (options.reverse, with_fees) {
+            (false, false) => vec![
+                target_code.clone(),
+                target.format_amount(breakdown.net_result),
+                target.format_rate(breakdown.mid_market_rate),
+            ],
+            (false, true) => vec![
+                target_code.clone(),
+                target.format_amount(breakdown.mid_market_result),
+                target.format_amount(breakdown.fees),
+                target.format_amount(breakdown.net_result),
+                target.format_rate(breakdown.effective_rate),
+            ],
+            (true, _) => vec![
+                target_code.clone(),
+                target.format_amount(breakdown.net_result),
+                source.format_amount(breakdown.amount),
+                target.format_rate(breakdown.mid_market_rate),
+            ],
+        };
+        table.add_row(row);
+    }
+
+    match options.reverse {
+        true => println!(
+            "{} needed to receive {} in each currency",
+            source.get_code().bold(),
+            amount.to_string().bold()
+        ),
+        false => println!("{} {} =", amount.to_string().bold(), source.get_code()),
     }
+    table.print();
+}
+
+fn print_breakdown(converter: &Converter, breakdown: &ConversionBreakdown) {
+    let source = converter.get_source();
+    let target = converter.get_target();
+    let fees = converter.get_fees();
+
+    let fixed_fee_code = match fees.fixed_fee_currency {
+        FeeCurrency::Source => source.get_code(),
+        FeeCurrency::Target => target.get_code(),
+    };
+
+    println!(
+        "  {:<12}{} {}  // exchange rate: {}",
+        "mid-market:",
+        target.format_amount(breakdown.mid_market_result),
+        target.get_code(),
+        target.format_rate(breakdown.mid_market_rate)
+    );
+    println!(
+        "  {:<12}{} {}  // spread: {}%, fixed fee: {} {}, minimum fee: {} {}",
+        "fees:",
+        target.format_amount(breakdown.fees).red(),
+        target.get_code(),
+        fees.spread_percent.normalize(),
+        fees.fixed_fee.normalize(),
+        fixed_fee_code,
+        fees.minimum_fee.normalize(),
+        target.get_code()
+    );
+    println!(
+        "  {:<12}{} {}  // effective rate: {}",
+        "net result:",
+        target.format_amount(breakdown.net_result).green(),
+        target.get_code(),
+        breakdown.effective_rate.normalize()
+    );
 }
 
 pub async fn validate_args(
-    source: &String,
-    target: &String,
-    amount: &String,
+    source: &str,
+    target: &str,
+    amount: &str,
+    options: &ConversionOptions,
 ) -> Result<(), String> {
-    let supported_currencies = get_supported_currencies().await;
+    let target_codes = get_target_codes(target);
 
-    if supported_currencies.is_err() {
-        return Ok(()); // we can't validate the currencies, validation will be transferred to api call
+    if target_codes.is_empty() {
+        return Err(format!("{} is not a valid currency", target));
     }
 
-    let supported_currencies = supported_currencies.unwrap();
+    let mut codes = vec![source.to_string()];
+    codes.extend(target_codes.iter().cloned());
+    let supported_currencies = get_validation_currencies(&codes).await;
 
-    let validations = vec![
-        validate(amount, ValidationType::Amount, None),
-        validate(
-            source,
-            ValidationType::Currency,
-            Some(&supported_currencies),
-        ),
-        validate(
-            target,
+    // the amount to receive is expressed in the target currencies
+    let amount_currencies = match options.reverse {
+        true => target_codes.clone(),
+        false => vec![source.to_string()],
+    };
+
+    let mut validations = vec![];
+
+    for code in amount_currencies {
+        validations.push(validate(
+            amount,
+            ValidationType::AmountIn(Currency::new_from_code(code)),
+            None,
+        ));
+    }
+
+    validations.push(validate(
+        source,
+        ValidationType::Currency,
+        Some(&supported_currencies),
+    ));
+
+    for target_code in &target_codes {
+        validations.push(validate(
+            target_code,
             ValidationType::Currency,
             Some(&supported_currencies),
-        ),
-    ];
+        ));
+    }
 
     let results = join_all(validations).await;
 
@@ -73,3 +295,14 @@ pub async fn validate_args(
 
     Ok(())
 }
+
+pub async fn validate_currency(currency: &str) -> Result<(), String> {
+    let supported_currencies = get_validation_currencies(&[currency.to_string()]).await;
+
+    validate(
+        currency,
+        ValidationType::Currency,
+        Some(&supported_currencies),
+    )
+    .await
+}
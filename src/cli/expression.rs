@@ -0,0 +1,94 @@
+use std::collections::HashMap;
+
+use colored::Colorize;
+use futures::future::join_all;
+
+use crate::{
+    conversion::{expression::ConversionExpression, rounding::RoundingMode},
+    currency::{get_rate, get_validation_currencies, history::CommandHistory},
+    validation::{validate, ValidationType},
+};
+
+pub async fn display_expression(input: &str) -> Result<CommandHistory, String> {
+    let expression = ConversionExpression::parse(input)?;
+    let target = expression.get_target();
+
+    let currencies: Vec<String> = expression
+        .get_currencies()
+        .into_iter()
+        .filter(|currency| currency != target.get_code())
+        .collect();
+
+    let get_rates = currencies.iter().map(|currency| async move {
+        let rate = get_rate(currency, target.get_code()).await;
+        (currency.clone(), rate)
+    });
+
+    let mut rates = HashMap::new();
+    for (currency, rate) in join_all(get_rates).await {
+        rates.insert(currency, rate?);
+    }
+
+    let result = expression.evaluate(&rates)?;
+
+    let rounding = RoundingMode::from_env();
+    let result = target.round_amount_with(result, rounding);
+
+    let rates_used = currencies
+        .iter()
+        .filter_map(|currency| {
+            rates
+                .get(currency)
+                .map(|rate| format!("1 {} = {} {}", currency, rate, target.get_code()))
+        })
+        .collect::<Vec<String>>();
+
+    let output = match rates_used.is_empty() {
+        true => format!(
+            "{} = {} {}",
+            input.trim(),
+            target.format_amount(result).green(),
+            target.get_code()
+        ),
+        false => format!(
+            "{} = {} {}  // exchange rates: {}",
+            input.trim(),
+            target.format_amount(result).green(),
+            target.get_code(),
+            rates_used.join(", ").yellow()
+        ),
+    };
+
+    println!("{}", output);
+
+    let summary = format!(
+        "{} = {} {}",
+        input.trim(),
+        target.format_amount(result),
+        target.get_code()
+    );
+    Ok(CommandHistory::new(input.trim().to_string(), Some(rounding)).with_summary(summary))
+}
+
+pub async fn validate_expression(input: &str) -> Result<(), String> {
+    let expression = ConversionExpression::parse(input)?;
+
+    let mut currencies = expression.get_currencies();
+    currencies.push(expression.get_target().get_code().clone());
+
+    let supported_currencies = get_validation_currencies(&currencies).await;
+
+    let validations = currencies.iter().map(|currency| {
+        validate(
+            currency,
+            ValidationType::Currency,
+            Some(&supported_currencies),
+        )
+    });
+
+    for result in join_all(validations).await {
+        result?;
+    }
+
+    Ok(())
+}
@@ -1,20 +1,467 @@
-use crate::{currency::history::get_history, error::print_error};
-
-pub async fn display_history() {
-    let history_commands = get_history().await;
-    match history_commands {
-        Ok(history_commands) => {
-            for command in history_commands {
-                println!(
-                    "Command: {} || Output: {} || time: {}",
-                    command.get_command(),
-                    command.get_output(),
-                    command.get_timestamp()
-                );
+use std::{
+    collections::HashMap,
+    fs,
+    io::{self, Write},
+};
+
+use chrono::NaiveDate;
+use futures::future::join_all;
+
+use crate::{
+    conversion::rounding::RoundingMode,
+    currency::{
+        get_rate_table,
+        history::{
+            search_history, CommandHistory, HistoryExportRow, HistoryFilter, HistoryOutcome,
+            HISTORY_EXPORT_HEADERS,
+        },
+        Currency,
+    },
+    error::{print_info, print_warning},
+};
+
+use super::{
+    history_stats::display_history_stats,
+    matrix::format_change_cell,
+    options::{CliFlag, OutputFormat},
+    table::Table,
+};
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum HistoryCommand {
+    List(HistoryFilter),
+    // a single entry, or every entry matching the filter
+    Replay {
+        id: Option<usize>,
+        filter: HistoryFilter,
+    },
+    Export {
+        format: OutputFormat,
+        output: Option<String>,
+        filter: HistoryFilter,
+    },
+    Delete {
+        id: usize,
+    },
+    Clear {
+        before: Option<NaiveDate>,
+    },
+    Pin {
+        id: usize,
+        pinned: bool,
+    },
+    Stats {
+        format: OutputFormat,
+        filter: HistoryFilter,
+    },
+}
+
+pub fn parse_history_command(args: &[String], flags: &[CliFlag]) -> Result<HistoryCommand, String> {
+    match args.first().map(String::as_str) {
+        None => Ok(HistoryCommand::List(get_history_filter(flags)?)),
+        Some("replay") => {
+            let filter = get_history_filter(flags)?;
+
+            match &args[1..] {
+                [] => Ok(HistoryCommand::Replay { id: None, filter }),
+                [id] if filter.is_empty() => Ok(HistoryCommand::Replay {
+                    id: Some(get_entry_id(id)?),
+                    filter,
+                }),
+                _ => Err(
+                    "Use history replay <id>, or history replay with filter options".to_string(),
+                ),
+            }
+        }
+        Some("export") if args.len() == 1 => {
+            let (format, flags) = OutputFormat::from_flags(
+                flags,
+                OutputFormat::Csv,
+                &[
+                    OutputFormat::Csv,
+                    OutputFormat::Json,
+                    OutputFormat::Markdown,
+                ],
+            )?;
+
+            let mut output = None;
+            let mut filter_flags = vec![];
+            for flag in flags {
+                match flag.name.as_str() {
+                    "output" => output = Some(flag.get_value()?.clone()),
+                    _ => filter_flags.push(flag),
+                }
+            }
+
+            Ok(HistoryCommand::Export {
+                format,
+                output,
+                filter: get_history_filter(&filter_flags)?,
+            })
+        }
+        Some("export") => {
+            Err("Use history export --format <csv|json|md> --output <file>".to_string())
+        }
+        Some("stats") if args.len() == 1 => {
+            let (format, flags) = OutputFormat::from_flags(
+                flags,
+                OutputFormat::Table,
+                &[OutputFormat::Table, OutputFormat::Json],
+            )?;
+
+            Ok(HistoryCommand::Stats {
+                format,
+                filter: get_history_filter(&flags)?,
+            })
+        }
+        Some("stats") => Err("Use history stats, with filter options".to_string()),
+        Some("delete") => match (&args[1..], flags) {
+            ([id], []) => Ok(HistoryCommand::Delete {
+                id: get_entry_id(id)?,
+            }),
+            _ => Err("Use history delete <id>".to_string()),
+        },
+        Some("clear") if args.len() == 1 => {
+            let mut before = None;
+            for flag in flags {
+                match flag.name.as_str() {
+                    "before" => before = Some(get_date(flag)?),
+                    _ => return Err(flag.unknown()),
+                }
             }
+
+            Ok(HistoryCommand::Clear { before })
+        }
+        Some("clear") => Err("Use history clear --before <YYYY-MM-DD>".to_string()),
+        Some(command @ ("pin" | "unpin")) => match (&args[1..], flags) {
+            ([id], []) => Ok(HistoryCommand::Pin {
+                id: get_entry_id(id)?,
+                pinned: command == "pin",
+            }),
+            _ => Err(format!("Use history {} <id>", command)),
+        },
+        Some(command) => Err(format!("Unknown history command {}", command)),
+    }
+}
+
+fn get_entry_id(id: &str) -> Result<usize, String> {
+    match id.trim_start_matches('#').parse::<usize>() {
+        Ok(id) if id > 0 => Ok(id),
+        _ => Err(format!("{} is not a valid history entry", id)),
+    }
+}
+
+pub fn get_history_filter(flags: &[CliFlag]) -> Result<HistoryFilter, String> {
+    let mut filter = HistoryFilter::default();
+
+    for flag in flags {
+        match flag.name.as_str() {
+            "source" => filter.source = Some(flag.get_value()?.to_uppercase()),
+            "target" => filter.target = Some(flag.get_value()?.to_uppercase()),
+            "pair" => filter = filter.with_pair(flag.get_value()?)?,
+            "since" => filter.since = Some(get_date(flag)?),
+            "until" => filter.until = Some(get_date(flag)?),
+            "min-amount" => filter.min_amount = Some(flag.get_decimal_value()?),
+            "max-amount" => filter.max_amount = Some(flag.get_decimal_value()?),
+            "search" => filter.text = Some(flag.get_value()?.clone()),
+            "last" => match flag.get_value()?.parse::<usize>() {
+                Ok(last) if last > 0 => filter.last = Some(last),
+                _ => return Err("Option --last must be a positive whole number".to_string()),
+            },
+            _ => return Err(flag.unknown()),
+        }
+    }
+
+    Ok(filter)
+}
+
+fn get_date(flag: &CliFlag) -> Result<NaiveDate, String> {
+    let value = flag.get_value()?;
+
+    NaiveDate::parse_from_str(value, "%Y-%m-%d").map_err(|_| {
+        format!(
+            "Option --{}: {} is not a valid date, use YYYY-MM-DD",
+            flag.name, value
+        )
+    })
+}
+
+pub async fn run_history_command(command: &HistoryCommand) -> Result<(), String> {
+    match command {
+        HistoryCommand::List(filter) => display_history(filter).await,
+        HistoryCommand::Replay { id, filter } => display_replay(*id, filter).await,
+        HistoryCommand::Export {
+            format,
+            output,
+            filter,
+        } => export_history(*format, output.as_deref(), filter).await,
+        HistoryCommand::Delete { id } => {
+            CommandHistory::delete(*id)?;
+            print_info(&format!("History entry {} deleted", id));
+            Ok(())
         }
-        Err(e) => {
-            print_error(e.as_str());
+        HistoryCommand::Clear { before } => {
+            let count = CommandHistory::clear(*before)?;
+            print_info(&format!("{} history entries deleted", count));
+            Ok(())
         }
+        HistoryCommand::Pin { id, pinned } => {
+            CommandHistory::set_pinned(*id, *pinned)?;
+            match pinned {
+                true => print_info(&format!("History entry {} pinned", id)),
+                false => print_info(&format!("History entry {} unpinned", id)),
+            }
+            Ok(())
+        }
+        HistoryCommand::Stats { format, filter } => display_history_stats(*format, filter).await,
+    }
+}
+
+pub async fn display_history(filter: &HistoryFilter) -> Result<(), String> {
+    let history_commands = search_history(filter).await?;
+
+    if history_commands.is_empty() && !filter.is_empty() {
+        print_info("No history entries match the given options");
+        return Ok(());
     }
+
+    for command in history_commands {
+        let rounding = match command.get_rounding() {
+            Some(rounding) => format!(" || rounding: {}", rounding),
+            None => "".to_string(),
+        };
+
+        let rates = match (command.get_rate_timestamp(), command.get_provider()) {
+            (Some(rate_timestamp), Some(provider)) => {
+                format!(" || rates: {} from {}", rate_timestamp, provider)
+            }
+            _ => "".to_string(),
+        };
+
+        let pinned = match command.is_pinned() {
+            true => " (pinned)",
+            false => "",
+        };
+
+        let outcome = match command.get_outcome() {
+            HistoryOutcome::Success => "".to_string(),
+            outcome => format!(" || outcome: {}", outcome),
+        };
+
+        println!(
+            "#{}{} Command: {} || Output: {} || time: {}{}{}{}",
+            command.get_id(),
+            pinned,
+            command.get_command(),
+            command.get_output(),
+            command.get_timestamp(),
+            rounding,
+            rates,
+            outcome
+        );
+    }
+
+    Ok(())
+}
+
+pub async fn display_replay(id: Option<usize>, filter: &HistoryFilter) -> Result<(), String> {
+    let entries = search_history(filter).await?;
+    let entries: Vec<CommandHistory> = match id {
+        Some(id) => match entries.into_iter().find(|entry| entry.get_id() == id) {
+            Some(entry) => vec![entry],
+            None => return Err(format!("History entry {} not found", id)),
+        },
+        None => entries,
+    };
+
+    let replayable = entries
+        .iter()
+        .filter(|entry| !entry.get_conversions().is_empty())
+        .count();
+    if replayable == 0 {
+        return Err("No conversions to replay".to_string());
+    }
+    if replayable < entries.len() {
+        print_warning(&format!(
+            "{} entries are not conversions and are skipped",
+            entries.len() - replayable
+        ));
+    }
+
+    let mut sources: Vec<String> = vec![];
+    for conversion in entries.iter().flat_map(|entry| entry.get_conversions()) {
+        if !sources.contains(&conversion.source) {
+            sources.push(conversion.source.clone());
+        }
+    }
+
+    // one rate table per source currency covers every replayed conversion
+    let get_rate_tables = sources.into_iter().map(|source| async move {
+        let rate_table = get_rate_table(&source).await;
+        (source, rate_table)
+    });
+    let rate_tables: HashMap<String, _> = join_all(get_rate_tables).await.into_iter().collect();
+
+    let mut table = Table::new(vec![
+        "Entry",
+        "Conversion",
+        "Original",
+        "Current",
+        "Difference",
+        "Change",
+    ]);
+
+    for entry in &entries {
+        let rounding = entry.get_rounding().unwrap_or_else(RoundingMode::from_env);
+
+        for conversion in entry.get_conversions() {
+            let description = match conversion.reverse {
+                true => format!(
+                    "{} needed for {} {}",
+                    conversion.source, conversion.result, conversion.target
+                ),
+                false => format!(
+                    "{} {} to {}",
+                    conversion.amount, conversion.source, conversion.target
+                ),
+            };
+
+            let replayed = match rate_tables.get(&conversion.source) {
+                Some(Ok(rate_table)) => conversion.replay(rate_table, rounding),
+                Some(Err(e)) => Err(e.clone()),
+                None => Err(format!(
+                    "Exchange rates for {} not found",
+                    conversion.source
+                )),
+            };
+
+            let row = match replayed {
+                Ok(replayed) => {
+                    let currency =
+                        Currency::new_from_code(conversion.get_compared_currency().clone());
+                    let difference = currency.format_amount(replayed.difference);
+
+                    vec![
+                        format!("#{}", entry.get_id()),
+                        description,
+                        format!(
+                            "{} {}",
+                            currency.format_amount(replayed.original),
+                            currency.get_code()
+                        ),
+                        format!(
+                            "{} {}",
+                            currency.format_amount(replayed.current),
+                            currency.get_code()
+                        ),
+                        match replayed.difference.is_sign_positive()
+                            && !replayed.difference.is_zero()
+                        {
+                            true => format!("+{}", difference),
+                            false => difference,
+                        },
+                        replayed
+                            .change_percent
+                            .map(format_change_cell)
+                            .unwrap_or_default(),
+                    ]
+                }
+                Err(e) => vec![format!("#{}", entry.get_id()), description, e],
+            };
+            table.add_row(row);
+        }
+    }
+
+    table.print();
+
+    Ok(())
+}
+
+pub fn write_history_export<W: Write>(
+    rows: &[HistoryExportRow],
+    format: OutputFormat,
+    mut writer: W,
+) -> Result<(), String> {
+    match format {
+        OutputFormat::Json => {
+            serde_json::to_writer_pretty(&mut writer, rows).map_err(|e| e.to_string())?;
+            writeln!(writer).map_err(|e| e.to_string())
+        }
+        OutputFormat::Markdown => {
+            let mut lines = vec![
+                format!("| {} |", HISTORY_EXPORT_HEADERS.join(" | ")),
+                format!("|{}", "---|".repeat(HISTORY_EXPORT_HEADERS.len())),
+            ];
+
+            for row in rows {
+                let cells: Vec<String> = get_export_cells(row)
+                    .iter()
+                    .map(|cell| cell.replace('|', "\\|"))
+                    .collect();
+                lines.push(format!("| {} |", cells.join(" | ")));
+            }
+
+            writeln!(writer, "{}", lines.join("\n")).map_err(|e| e.to_string())
+        }
+        _ => {
+            let mut writer = csv::Writer::from_writer(writer);
+
+            writer
+                .write_record(HISTORY_EXPORT_HEADERS)
+                .map_err(|e| e.to_string())?;
+
+            for row in rows {
+                writer
+                    .write_record(get_export_cells(row))
+                    .map_err(|e| e.to_string())?;
+            }
+
+            writer.flush().map_err(|e| e.to_string())
+        }
+    }
+}
+
+// cells in the order of HISTORY_EXPORT_HEADERS
+fn get_export_cells(row: &HistoryExportRow) -> Vec<String> {
+    vec![
+        row.id.to_string(),
+        row.timestamp.clone(),
+        row.command.clone(),
+        row.source.clone().unwrap_or_default(),
+        row.target.clone().unwrap_or_default(),
+        row.amount.clone().unwrap_or_default(),
+        row.rate.clone().unwrap_or_default(),
+        row.result.clone().unwrap_or_default(),
+        row.reverse
+            .map(|reverse| reverse.to_string())
+            .unwrap_or_default(),
+        row.rounding.clone().unwrap_or_default(),
+        row.rate_timestamp.clone().unwrap_or_default(),
+        row.provider.clone().unwrap_or_default(),
+        row.summary.clone().unwrap_or_default(),
+    ]
+}
+
+pub async fn export_history(
+    format: OutputFormat,
+    output: Option<&str>,
+    filter: &HistoryFilter,
+) -> Result<(), String> {
+    let rows: Vec<HistoryExportRow> = search_history(filter)
+        .await?
+        .iter()
+        .flat_map(|entry| entry.get_export_rows())
+        .collect();
+
+    match output {
+        Some(path) => {
+            let file =
+                fs::File::create(path).map_err(|e| format!("Could not write {}: {}", path, e))?;
+            write_history_export(&rows, format, file)?;
+            print_info(&format!("Exported {} rows to {}", rows.len(), path));
+        }
+        None => write_history_export(&rows, format, io::stdout())?,
+    }
+
+    Ok(())
 }
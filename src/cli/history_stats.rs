@@ -0,0 +1,228 @@
+use std::{cmp::Reverse, collections::HashMap};
+
+use chrono::Datelike;
+use colored::Colorize;
+use rust_decimal::Decimal;
+use serde::Serialize;
+
+use crate::{
+    currency::{
+        history::{search_history, CommandHistory, HistoryFilter},
+        Currency,
+    },
+    error::print_info,
+};
+
+use super::{options::OutputFormat, table::Table};
+
+// rates are averaged exactly, only their display is shortened
+const RATE_DECIMAL_PLACES: u32 = 6;
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct PairStats {
+    pub source: String,
+    pub target: String,
+    pub conversions: usize,
+    pub volume: Decimal,
+    // the rate obtained after fees, weighted by the converted amounts
+    pub average_rate: Decimal,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct CurrencyVolume {
+    pub currency: String,
+    pub converted: Decimal,
+    pub received: Decimal,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct PeriodCount {
+    pub period: String,
+    pub conversions: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct HistoryStats {
+    pub entries: usize,
+    pub conversions: usize,
+    pub first_timestamp: Option<String>,
+    pub last_timestamp: Option<String>,
+    pub pairs: Vec<PairStats>,
+    pub volumes: Vec<CurrencyVolume>,
+    pub per_day: Vec<PeriodCount>,
+    pub per_week: Vec<PeriodCount>,
+}
+
+// pairs are ordered by use, volumes by currency and periods by date
+pub fn get_history_stats(history: &[CommandHistory]) -> HistoryStats {
+    let mut pairs: Vec<PairStats> = vec![];
+    // the effective rate result / amount weighted by the amount is the result itself
+    let mut received: HashMap<(String, String), Decimal> = HashMap::new();
+    let mut volumes: HashMap<String, CurrencyVolume> = HashMap::new();
+    let mut per_day: HashMap<String, usize> = HashMap::new();
+    let mut per_week: HashMap<String, usize> = HashMap::new();
+    let mut conversions = 0;
+
+    for entry in history {
+        let datetime = entry.get_datetime();
+
+        for conversion in entry.get_conversions() {
+            conversions += 1;
+
+            let key = (conversion.source.clone(), conversion.target.clone());
+            match pairs
+                .iter_mut()
+                .find(|pair| pair.source == key.0 && pair.target == key.1)
+            {
+                Some(pair) => {
+                    pair.conversions += 1;
+                    pair.volume += conversion.amount;
+                }
+                None => pairs.push(PairStats {
+                    source: key.0.clone(),
+                    target: key.1.clone(),
+                    conversions: 1,
+                    volume: conversion.amount,
+                    average_rate: Decimal::ZERO,
+                }),
+            }
+            *received.entry(key).or_insert(Decimal::ZERO) += conversion.result;
+
+            volumes
+                .entry(conversion.source.clone())
+                .or_insert_with(|| CurrencyVolume::new(&conversion.source))
+                .converted += conversion.amount;
+            volumes
+                .entry(conversion.target.clone())
+                .or_insert_with(|| CurrencyVolume::new(&conversion.target))
+                .received += conversion.result;
+
+            if let Some(datetime) = datetime {
+                let date = datetime.date_naive();
+                let week = date.iso_week();
+
+                *per_day.entry(date.to_string()).or_insert(0) += 1;
+                *per_week
+                    .entry(format!("{}-W{:02}", week.year(), week.week()))
+                    .or_insert(0) += 1;
+            }
+        }
+    }
+
+    for pair in pairs.iter_mut() {
+        let received = received[&(pair.source.clone(), pair.target.clone())];
+
+        pair.average_rate = match pair.volume.is_zero() {
+            true => Decimal::ZERO,
+            false => received / pair.volume,
+        };
+    }
+    // the sort is stable, pairs used as often stay in the order they were first used
+    pairs.sort_by_key(|pair| Reverse(pair.conversions));
+
+    let mut volumes: Vec<CurrencyVolume> = volumes.into_values().collect();
+    volumes.sort_by(|a, b| a.currency.cmp(&b.currency));
+
+    HistoryStats {
+        entries: history.len(),
+        conversions,
+        first_timestamp: history.first().map(|entry| entry.get_timestamp().clone()),
+        last_timestamp: history.last().map(|entry| entry.get_timestamp().clone()),
+        pairs,
+        volumes,
+        per_day: get_period_counts(per_day),
+        per_week: get_period_counts(per_week),
+    }
+}
+
+impl CurrencyVolume {
+    fn new(currency: &str) -> CurrencyVolume {
+        CurrencyVolume {
+            currency: currency.to_string(),
+            converted: Decimal::ZERO,
+            received: Decimal::ZERO,
+        }
+    }
+}
+
+fn get_period_counts(counts: HashMap<String, usize>) -> Vec<PeriodCount> {
+    let mut counts: Vec<PeriodCount> = counts
+        .into_iter()
+        .map(|(period, conversions)| PeriodCount {
+            period,
+            conversions,
+        })
+        .collect();
+    counts.sort_by(|a, b| a.period.cmp(&b.period));
+
+    counts
+}
+
+pub async fn display_history_stats(
+    format: OutputFormat,
+    filter: &HistoryFilter,
+) -> Result<(), String> {
+    let stats = get_history_stats(&search_history(filter).await?);
+
+    if format == OutputFormat::Json {
+        let json = serde_json::to_string_pretty(&stats).map_err(|e| e.to_string())?;
+        println!("{}", json);
+        return Ok(());
+    }
+
+    if stats.conversions == 0 {
+        print_info("No conversions in the history to report on");
+        return Ok(());
+    }
+
+    if let (Some(first), Some(last)) = (&stats.first_timestamp, &stats.last_timestamp) {
+        println!(
+            "{} conversions in {} entries, from {} to {}",
+            stats.conversions, stats.entries, first, last
+        );
+    }
+
+    println!("\n{}", "Currency pairs".green());
+    let mut table = Table::new(vec!["Pair", "Conversions", "Volume", "Average rate"]);
+    for pair in &stats.pairs {
+        let source = Currency::new_from_code(pair.source.clone());
+
+        table.add_row(vec![
+            format!("{}/{}", pair.source, pair.target),
+            pair.conversions.to_string(),
+            format!("{} {}", source.format_amount(pair.volume), pair.source),
+            pair.average_rate
+                .round_dp(RATE_DECIMAL_PLACES)
+                .normalize()
+                .to_string(),
+        ]);
+    }
+    table.print();
+
+    println!("\n{}", "Volume per currency".green());
+    let mut table = Table::new(vec!["Currency", "Converted", "Received"]);
+    for volume in &stats.volumes {
+        let currency = Currency::new_from_code(volume.currency.clone());
+
+        table.add_row(vec![
+            volume.currency.clone(),
+            currency.format_amount(volume.converted),
+            currency.format_amount(volume.received),
+        ]);
+    }
+    table.print();
+
+    for (title, header, counts) in [
+        ("Conversions per day", "Day", &stats.per_day),
+        ("Conversions per week", "Week", &stats.per_week),
+    ] {
+        println!("\n{}", title.green());
+        let mut table = Table::new(vec![header, "Conversions"]);
+        for count in counts {
+            table.add_row(vec![count.period.clone(), count.conversions.to_string()]);
+        }
+        table.print();
+    }
+
+    Ok(())
+}
@@ -1,11 +1,8 @@
 use std::io;
 
-use crate::{
-    cli::Action,
-    error::{print_error, print_info},
-};
+use crate::{cli::Action, error::print_info};
 
-use super::direct_conversion::{display_direct_conversion, validate_args};
+use super::options::ConversionOptions;
 
 pub async fn start_interactive_mode() {
     print_info("Welcome to the interactive mode! You can type 'help' to see the list of available commands.");
@@ -14,23 +11,32 @@ pub async fn start_interactive_mode() {
     loop {
         println!("Enter a command or 'exit' to quit // Type 'help' to see the list of available commands:");
         input.clear();
-        io::stdin().read_line(&mut input).unwrap();
+        // stdin was closed, e.g. at the end of piped input
+        if let Ok(0) | Err(_) = io::stdin().read_line(&mut input) {
+            return print_info("Goodbye!");
+        }
+
         let input = input.trim();
         if input == "exit" {
             return print_info("Goodbye!");
         }
 
+        if input.is_empty() {
+            continue;
+        }
+
         Action::from_interactive_mode(input).await;
     }
 }
 
-pub async fn process_direct_conversion() {
+// the action and the values entered, which are recorded as its arguments
+pub fn read_direct_conversion() -> (Action, Vec<String>) {
     print_info("Welcome to the direct conversion mode!");
 
     let input_desc = vec![
         "Enter the source currency code:",
-        "Enter the target currency code:",
-        "Enter the amount to convert:",
+        "Enter the target currency code (separate several codes with commas):",
+        "Enter the amount to convert (or 'receive <amount>' to get the amount needed to receive it):",
     ];
     let mut inputs: Vec<String> = vec![];
 
@@ -42,15 +48,20 @@ pub async fn process_direct_conversion() {
         inputs.push(input.to_string());
     }
 
-    let validate_inputs = validate_args(&inputs[0], &inputs[1], &inputs[2]).await;
+    let mut options = ConversionOptions::default();
+    let mut amount = inputs[2].clone();
 
-    match validate_inputs {
-        Ok(_) => (),
-        Err(e) => {
-            print_error(e.as_str());
-            return;
-        }
+    if let Some(receive_amount) = inputs[2].strip_prefix("receive") {
+        options.reverse = true;
+        amount = receive_amount.trim().to_string();
     }
 
-    display_direct_conversion(&inputs[0], &inputs[1], &inputs[2]).await;
+    let action = Action::DirectConversion {
+        source: inputs[0].clone(),
+        target: inputs[1].clone(),
+        amount,
+        options,
+    };
+
+    (action, inputs)
 }
@@ -1,31 +1,240 @@
-use crate::{currency::get_supported_currencies_with_rates, error::print_error};
+use std::{cmp::Ordering, str::FromStr};
+
+use crate::{
+    currency::{
+        get_rate_table, get_supported_currencies, get_validation_currencies, Currency, RateTable,
+    },
+    error::print_info,
+    validation::{validate, ValidationType},
+};
 use colored::Colorize;
+use futures::future::join_all;
+use rust_decimal::Decimal;
+
+use super::{direct_conversion::get_target_codes, options::CliFlag, table::Table};
+
+const DEFAULT_PAGE_SIZE: usize = 50;
+
+#[derive(Debug, PartialEq, Clone, Copy, Default)]
+pub enum RateSort {
+    #[default]
+    Code,
+    Rate,
+}
+
+impl FromStr for RateSort {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "code" => Ok(RateSort::Code),
+            "rate" => Ok(RateSort::Rate),
+            _ => Err(format!("{} is not a valid sort order, use code or rate", s)),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Clone, Default)]
+pub struct RateListOptions {
+    pub bases: Option<Vec<String>>,
+    pub targets: Option<Vec<String>>,
+    pub sort: RateSort,
+    pub top: Option<usize>,
+    pub page: Option<usize>,
+    pub page_size: Option<usize>,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct RateRow {
+    pub base: String,
+    pub target: String,
+    pub rate: Decimal,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct RatePage {
+    pub rows: Vec<RateRow>,
+    pub page: usize,
+    pub pages: usize,
+}
+
+impl RateListOptions {
+    pub fn from_flags(flags: &[CliFlag]) -> Result<RateListOptions, String> {
+        let mut options = RateListOptions::default();
+
+        for flag in flags {
+            match flag.name.as_str() {
+                "base" => options.bases = Some(get_target_codes(flag.get_value()?)),
+                "targets" => options.targets = Some(get_target_codes(flag.get_value()?)),
+                "sort" => options.sort = flag.get_value()?.parse::<RateSort>()?,
+                "top" => options.top = Some(get_positive_number(flag)?),
+                "page" => options.page = Some(get_positive_number(flag)?),
+                "page-size" => options.page_size = Some(get_positive_number(flag)?),
+                _ => return Err(flag.unknown()),
+            }
+        }
+
+        Ok(options)
+    }
+
+    pub fn get_page_size(&self) -> Option<usize> {
+        match (self.page, self.page_size) {
+            (_, Some(page_size)) => Some(page_size),
+            (Some(_), None) => Some(DEFAULT_PAGE_SIZE),
+            (None, None) => None,
+        }
+    }
+
+    // bases are sorted by code, the targets of each base by the chosen order
+    pub fn select_rates(&self, rate_tables: &[RateTable]) -> RatePage {
+        let mut rate_tables: Vec<&RateTable> = rate_tables.iter().collect();
+        rate_tables.sort_by(|a, b| a.get_base().get_code().cmp(b.get_base().get_code()));
+
+        let mut rows: Vec<RateRow> = vec![];
+
+        for rate_table in rate_tables {
+            let base = rate_table.get_base().get_code();
+
+            let mut targets: Vec<RateRow> = rate_table
+                .get_rates()
+                .iter()
+                .filter(|(target, _)| match &self.targets {
+                    Some(targets) => targets.contains(target),
+                    None => true,
+                })
+                .map(|(target, rate)| RateRow {
+                    base: base.clone(),
+                    target: target.clone(),
+                    rate: *rate,
+                })
+                .collect();
+
+            targets.sort_by(|a, b| match self.sort {
+                RateSort::Code => a.target.cmp(&b.target),
+                RateSort::Rate => match a.rate.cmp(&b.rate) {
+                    Ordering::Equal => a.target.cmp(&b.target),
+                    ordering => ordering,
+                },
+            });
+
+            if let Some(top) = self.top {
+                targets.truncate(top);
+            }
+
+            rows.append(&mut targets);
+        }
 
-pub async fn display_supported_currencies_with_rates() {
-    let supported_currencies_with_rates = get_supported_currencies_with_rates().await;
-
-    match supported_currencies_with_rates {
-        Ok(currencies) => {
-            for currency in currencies {
-                for (code, rates) in currency {
-                    println!("{}", code.green());
-                    for (index, (target, rate)) in rates.iter().enumerate() {
-                        if index == 0 || index % 5 == 0 {
-                            print!("{}: {}", target.yellow(), rate);
-                        } else {
-                            print!(" {}: {}", target.yellow(), rate);
-                        }
-                        if (index + 1) % 5 == 0 {
-                            println!(" ");
-                        }
-                    }
-                    println!(" ");
-                    println!(" ");
+        let page_size = match self.get_page_size() {
+            Some(page_size) => page_size,
+            None => {
+                return RatePage {
+                    rows,
+                    page: 1,
+                    pages: 1,
                 }
             }
+        };
+
+        let pages = rows.len().div_ceil(page_size).max(1);
+        let page = self.page.unwrap_or(1);
+
+        RatePage {
+            rows: rows
+                .into_iter()
+                .skip((page - 1).saturating_mul(page_size))
+                .take(page_size)
+                .collect(),
+            page,
+            pages,
         }
-        Err(e) => {
-            print_error(e.to_string().as_str());
+    }
+}
+
+fn get_positive_number(flag: &CliFlag) -> Result<usize, String> {
+    match flag.get_value()?.parse::<usize>() {
+        Ok(number) if number > 0 => Ok(number),
+        _ => Err(format!(
+            "Option --{} must be a positive whole number",
+            flag.name
+        )),
+    }
+}
+
+pub async fn display_supported_currencies_with_rates(
+    options: &RateListOptions,
+) -> Result<(), String> {
+    let bases = match &options.bases {
+        Some(bases) => bases.clone(),
+        None => get_supported_currencies()
+            .await?
+            .iter()
+            .map(|currency| currency.get_code().clone())
+            .collect(),
+    };
+
+    if options.bases.is_none() {
+        print_info("Getting exchange rates for supported currencies...");
+    }
+
+    let mut rate_tables: Vec<RateTable> = vec![];
+    for rate_table in join_all(bases.iter().map(|base| get_rate_table(base))).await {
+        rate_tables.push(rate_table?);
+    }
+
+    let page = options.select_rates(&rate_tables);
+
+    if page.rows.is_empty() {
+        return match page.page > page.pages {
+            true => Err(format!(
+                "Page {} is empty, there are {} pages",
+                page.page, page.pages
+            )),
+            false => Err("No rates match the given options".to_string()),
+        };
+    }
+
+    let mut groups: Vec<(String, Table)> = vec![];
+
+    for row in page.rows {
+        if groups.last().is_none_or(|(base, _)| *base != row.base) {
+            groups.push((row.base.clone(), Table::new(vec!["Target", "Rate"])));
+        }
+
+        if let Some((_, table)) = groups.last_mut() {
+            let rate = Currency::new_from_code(row.target.clone()).format_rate(row.rate);
+            table.add_row(vec![row.target, rate]);
+        }
+    }
+
+    for (index, (base, table)) in groups.iter().enumerate() {
+        if index > 0 {
+            println!(" ");
         }
+
+        println!("{}", base.green());
+        table.print();
+    }
+
+    if options.get_page_size().is_some() {
+        println!(" ");
+        println!("Page {} of {}", page.page, page.pages);
+    }
+
+    Ok(())
+}
+
+pub async fn validate_rate_list_options(options: &RateListOptions) -> Result<(), String> {
+    let bases = match &options.bases {
+        Some(bases) if !bases.is_empty() => bases,
+        Some(_) => return Err("Option --base requires at least one currency".to_string()),
+        None => return Ok(()),
+    };
+
+    let supported_currencies = get_validation_currencies(bases).await;
+
+    for base in bases {
+        validate(base, ValidationType::Currency, Some(&supported_currencies)).await?;
     }
+
+    Ok(())
 }
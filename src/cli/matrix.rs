@@ -0,0 +1,271 @@
+use colored::Colorize;
+use rust_decimal::Decimal;
+use serde::Serialize;
+
+use crate::{
+    conversion::rounding::RoundingMode,
+    currency::{
+        get_rate_table, get_validation_currencies, snapshot::RateSnapshots, Currency, RateTable,
+    },
+    error::print_info,
+    validation::{validate, ValidationType},
+};
+
+use super::{options::OutputFormat, table::Table};
+
+const RATE_DECIMAL_PLACES: u32 = 6;
+const CHANGE_DECIMAL_PLACES: u32 = 2;
+const MISSING_CELL: &str = "-";
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct MatrixView {
+    pub mode: String,
+    pub inverted: bool,
+    pub rate_timestamp: Option<String>,
+    pub compared_with: Option<String>,
+    pub currencies: Vec<String>,
+    // rows are the currencies converted from, columns the currencies converted into
+    pub rows: Vec<Vec<Option<String>>>,
+}
+
+pub fn get_matrix_currencies(currencies: &str) -> Vec<String> {
+    let mut codes: Vec<String> = vec![];
+
+    for code in currencies.split(',') {
+        let code = code.trim().to_uppercase();
+
+        if !code.is_empty() && !codes.contains(&code) {
+            codes.push(code);
+        }
+    }
+
+    codes
+}
+
+// cell [i][j] is the amount of currency j for one unit of currency i, inverted it is the reverse
+pub fn build_rate_matrix(
+    currencies: &[String],
+    rate_table: &RateTable,
+    invert: bool,
+) -> Result<Vec<Vec<Decimal>>, String> {
+    currencies
+        .iter()
+        .map(|row| {
+            currencies
+                .iter()
+                .map(|column| match invert {
+                    true => rate_table.get_cross_rate(column, row),
+                    false => rate_table.get_cross_rate(row, column),
+                })
+                .collect()
+        })
+        .collect()
+}
+
+// percentage change of every cell, None when the earlier rate is zero
+pub fn build_change_matrix(
+    current: &[Vec<Decimal>],
+    previous: &[Vec<Decimal>],
+) -> Vec<Vec<Option<Decimal>>> {
+    current
+        .iter()
+        .zip(previous)
+        .map(|(current, previous)| {
+            current
+                .iter()
+                .zip(previous)
+                .map(|(current, previous)| match previous.is_zero() {
+                    true => None,
+                    false => Some((current - previous) / previous * Decimal::ONE_HUNDRED),
+                })
+                .collect()
+        })
+        .collect()
+}
+
+pub fn format_rate_cell(rate: Decimal) -> String {
+    RoundingMode::HalfEven
+        .round(rate, RATE_DECIMAL_PLACES)
+        .normalize()
+        .to_string()
+}
+
+pub fn format_change_cell(change: Decimal) -> String {
+    let change = RoundingMode::HalfEven.round(change, CHANGE_DECIMAL_PLACES);
+
+    match change.is_zero() {
+        true => format!("{}%", change.abs()),
+        false if change.is_sign_positive() => format!("+{}%", change),
+        false => format!("{}%", change),
+    }
+}
+
+impl MatrixView {
+    fn get_cell(cell: &Option<String>) -> &str {
+        cell.as_deref().unwrap_or(MISSING_CELL)
+    }
+
+    pub fn to_table(&self) -> Table {
+        let corner = match self.inverted {
+            true => "to \\ from",
+            false => "from \\ to",
+        };
+        let mut headers = vec![corner];
+        headers.extend(self.currencies.iter().map(|currency| currency.as_str()));
+
+        let mut table = Table::new(headers);
+
+        for (currency, row) in self.currencies.iter().zip(&self.rows) {
+            let mut cells = vec![currency.clone()];
+            cells.extend(
+                row.iter()
+                    .map(|cell| MatrixView::get_cell(cell).to_string()),
+            );
+            table.add_row(cells);
+        }
+
+        table
+    }
+
+    pub fn to_csv(&self) -> Result<String, String> {
+        let mut writer = csv::Writer::from_writer(vec![]);
+
+        let mut headers = vec![""];
+        headers.extend(self.currencies.iter().map(|currency| currency.as_str()));
+        writer.write_record(headers).map_err(|e| e.to_string())?;
+
+        for (currency, row) in self.currencies.iter().zip(&self.rows) {
+            let mut cells = vec![currency.as_str()];
+            cells.extend(row.iter().map(|cell| cell.as_deref().unwrap_or("")));
+            writer.write_record(cells).map_err(|e| e.to_string())?;
+        }
+
+        let csv = writer.into_inner().map_err(|e| e.to_string())?;
+        String::from_utf8(csv).map_err(|e| e.to_string())
+    }
+
+    pub fn to_markdown(&self) -> String {
+        let mut lines = vec![
+            format!("| | {} |", self.currencies.join(" | ")),
+            format!("|---|{}", "---:|".repeat(self.currencies.len())),
+        ];
+
+        for (currency, row) in self.currencies.iter().zip(&self.rows) {
+            let cells: Vec<&str> = row.iter().map(MatrixView::get_cell).collect();
+            lines.push(format!("| **{}** | {} |", currency, cells.join(" | ")));
+        }
+
+        lines.join("\n")
+    }
+}
+
+pub async fn display_matrix(
+    currencies: &[String],
+    invert: bool,
+    change: bool,
+    format: OutputFormat,
+) -> Result<(), String> {
+    let base = Currency::new_from_code(currencies[0].clone());
+
+    // cross rates of every pair are derived from a single rate table
+    let rate_table = get_rate_table(base.get_code()).await?;
+    let rates = build_rate_matrix(currencies, &rate_table, invert)?;
+
+    let mut snapshots = RateSnapshots::load(&base);
+    let earlier = snapshots.record(&rate_table);
+    let _ = snapshots.save(&base);
+
+    let rows: Vec<Vec<Option<String>>> = match change {
+        true => {
+            let previous = earlier.as_ref().and_then(|earlier| {
+                build_rate_matrix(currencies, &earlier.get_rate_table(), invert).ok()
+            });
+
+            match previous {
+                Some(previous) => build_change_matrix(&rates, &previous)
+                    .into_iter()
+                    .map(|row| {
+                        row.into_iter()
+                            .map(|cell| cell.map(format_change_cell))
+                            .collect()
+                    })
+                    .collect(),
+                None => {
+                    print_info("No earlier snapshot of these rates yet, the change is shown once the rates are updated");
+                    currencies
+                        .iter()
+                        .map(|_| vec![None; currencies.len()])
+                        .collect()
+                }
+            }
+        }
+        false => rates
+            .into_iter()
+            .map(|row| {
+                row.into_iter()
+                    .map(|cell| Some(format_rate_cell(cell)))
+                    .collect()
+            })
+            .collect(),
+    };
+
+    let view = MatrixView {
+        mode: match change {
+            true => "change".to_string(),
+            false => "rates".to_string(),
+        },
+        inverted: invert,
+        rate_timestamp: rate_table
+            .get_updated_at()
+            .map(|updated_at| updated_at.to_rfc3339()),
+        compared_with: match change {
+            true => earlier.map(|earlier| earlier.get_taken_at().clone()),
+            false => None,
+        },
+        currencies: currencies.to_vec(),
+        rows,
+    };
+
+    match format {
+        OutputFormat::Csv => print!("{}", view.to_csv()?),
+        OutputFormat::Markdown => println!("{}", view.to_markdown()),
+        OutputFormat::Json => println!(
+            "{}",
+            serde_json::to_string_pretty(&view).map_err(|e| e.to_string())?
+        ),
+        OutputFormat::Table => {
+            let description = match (change, &view.compared_with) {
+                (true, Some(compared_with)) => {
+                    format!("Change of the cross rates since {}", compared_with)
+                }
+                (true, None) => "Change of the cross rates".to_string(),
+                (false, _) => "Cross rates".to_string(),
+            };
+            println!("{}", description.bold());
+            view.to_table().print();
+        }
+    }
+
+    Ok(())
+}
+
+pub async fn validate_matrix_currencies(currencies: &[String]) -> Result<(), String> {
+    if currencies.len() < 2 {
+        return Err(
+            "A matrix needs at least two currencies, example: matrix USD,EUR,GBP".to_string(),
+        );
+    }
+
+    let supported_currencies = get_validation_currencies(currencies).await;
+
+    for currency in currencies {
+        validate(
+            currency,
+            ValidationType::Currency,
+            Some(&supported_currencies),
+        )
+        .await?;
+    }
+
+    Ok(())
+}
@@ -1,22 +1,49 @@
+use std::time::{Duration, Instant};
+
 use colored::Colorize;
 
-use crate::error::print_error;
+use crate::{
+    api::is_offline,
+    conversion::{expression::is_conversion_expression, rounding::RoundingMode},
+    currency::history::{CommandHistory, HistoryFilter, HistoryOutcome, HistoryRecording},
+    error::{print_error, print_verbose},
+};
 
 use self::{
-    direct_conversion::{display_direct_conversion, validate_args},
-    history::display_history,
-    interactive_mode::{process_direct_conversion, start_interactive_mode},
+    batch::display_batch,
+    direct_conversion::{display_direct_conversion, validate_args, validate_currency},
+    expression::{display_expression, validate_expression},
+    history::{display_history, parse_history_command, run_history_command, HistoryCommand},
+    interactive_mode::{read_direct_conversion, start_interactive_mode},
     list_currencies::display_supported_currencies,
-    list_currencies_with_rates::display_supported_currencies_with_rates,
+    list_currencies_with_rates::{
+        display_supported_currencies_with_rates, validate_rate_list_options, RateListOptions,
+    },
+    matrix::{display_matrix, get_matrix_currencies, validate_matrix_currencies},
+    natural_input::parse_natural_conversion,
+    options::{split_flags, CliFlag, ConversionOptions, GlobalOptions, OutputFormat},
+    parser::{parse_command, print_command_help, print_help},
+    portfolio::display_portfolio_value,
+    search::{display_search, get_search_limit},
     setup::setup_app,
     update_cache::update_cache,
 };
+pub mod batch;
 pub mod direct_conversion;
+pub mod expression;
 pub mod history;
+pub mod history_stats;
 pub mod interactive_mode;
 pub mod list_currencies;
 pub mod list_currencies_with_rates;
+pub mod matrix;
+pub mod natural_input;
+pub mod options;
+pub mod parser;
+pub mod portfolio;
+pub mod search;
 pub mod setup;
+pub mod table;
 pub mod update_cache;
 
 #[derive(Debug, PartialEq)]
@@ -26,144 +53,279 @@ pub enum Action {
         source: String,
         target: String,
         amount: String,
+        options: ConversionOptions,
+    },
+    Expression {
+        expression: String,
+    },
+    Batch {
+        input: Option<String>,
+        format: OutputFormat,
+        options: ConversionOptions,
+    },
+    Value {
+        input: String,
+        currency: String,
+        format: OutputFormat,
+        rounding: Option<RoundingMode>,
+    },
+    Matrix {
+        currencies: Vec<String>,
+        invert: bool,
+        change: bool,
+        format: OutputFormat,
+    },
+    Search {
+        query: String,
+        limit: usize,
     },
     UpdateCache,
     Help,
+    CommandHelp {
+        command: String,
+    },
     Error {
         message: String,
     },
     ListCurrencies,
-    ListCurrenciesWithRates,
+    ListCurrenciesWithRates {
+        options: RateListOptions,
+    },
     Setup,
     History,
+    HistoryCommand {
+        command: HistoryCommand,
+    },
 }
 
 impl Action {
     async fn from_interactive_mode(input: &str) {
-        match input {
-            "direct-conversion" => process_direct_conversion().await,
-            "update-cache" => update_cache().await,
-            "help" => Action::print_help_interactive_mode(),
-            "list-currencies" => display_supported_currencies().await,
-            "list-currencies-with-rates" => display_supported_currencies_with_rates().await,
-            "setup" => setup_app().await,
-            "history" => display_history().await,
-            _ => print_error("Invalid command"),
-        }
-    }
+        let arguments: Vec<String> = input.split_whitespace().map(String::from).collect();
+        let (args, flags) = split_flags(&arguments);
+        let (options, flags) = GlobalOptions::from_flags(&flags);
 
-    fn get_interactive_mode_actions() -> Vec<Action> {
-        Action::get_all_actions()
-            .into_iter()
-            .filter(|action| !matches!(action, Action::InteractiveMode))
-            .collect::<Vec<Action>>()
+        let action = match input {
+            "direct-conversion" => {
+                let (action, arguments) = read_direct_conversion();
+                return Box::pin(action.execute(&arguments)).await;
+            }
+            "help" => return Action::print_help_interactive_mode(),
+            // the same commands as on the command line, except interactive mode itself
+            _ => match parse_arguments(&args, &flags) {
+                Action::InteractiveMode => Action::Error {
+                    message: "Already in interactive mode".to_string(),
+                },
+                action => action,
+            },
+        };
+
+        // global options given with a command only apply to it
+        let session = GlobalOptions::current();
+        session.merge(&options).apply();
+
+        // interactive mode is itself run by execute
+        Box::pin(action.execute(&arguments)).await;
+
+        session.apply();
     }
 
     fn print_help_interactive_mode() {
-        let actions = Action::get_interactive_mode_actions();
-        for action in actions {
-            action.help_print_interactive_mode();
-        }
+        println!(
+            "{} - Direct conversion, the currencies and the amount are asked for",
+            "direct-conversion".green()
+        );
+        println!("{} - Leave interactive mode", "exit".green());
+        println!(" ");
+        print_help();
     }
 
-    fn help_print_interactive_mode(&self) {
-        match self {
-            Action::DirectConversion { .. } => {
-                println!("{} - Direct conversion", "direct-conversion".green())
-            }
-            Action::UpdateCache => println!("{} - Update cache", "update-cache".green()),
-            Action::Help => println!("{} - Print help", "help".green()),
-            Action::ListCurrencies => {
-                println!("{} - List supported currencies", "list-currencies".green(),)
-            }
-            Action::ListCurrenciesWithRates => println!(
-                "{} - List supported currencies with rates",
-                "list-currencies-with-rates".green(),
-            ),
-            Action::Setup => println!("{} - Setup application", "setup".green()),
-            Action::History => println!("{} - Display history", "history".green()),
-            _ => (),
+    fn new_direct_conversion(
+        source: String,
+        target: String,
+        amount: String,
+        options: ConversionOptions,
+    ) -> Action {
+        Action::DirectConversion {
+            source,
+            target,
+            amount,
+            options,
         }
     }
 
-    fn print_help() {
-        let actions = Action::get_all_actions();
-        for action in actions {
-            action.help_print();
-        }
+    // None when the arguments are not a conversion
+    fn new_conversion(args: &[String], flags: &[CliFlag]) -> Option<Action> {
+        let receive_amount = flags
+            .iter()
+            .find(|flag| flag.name == "receive")
+            .and_then(|flag| flag.value.clone());
+
+        let conversion = match (args.len(), receive_amount) {
+            (2, Some(receive_amount)) => Some((args[0].clone(), args[1].clone(), receive_amount)),
+            (3, Some(_)) => {
+                return Some(Action::Error {
+                    message: "Amount provided twice, use <source> <target> --receive <amount>"
+                        .to_string(),
+                })
+            }
+            (_, None) => match parse_natural_conversion(&args.join(" ")) {
+                Ok(conversion) => Some((conversion.source, conversion.target, conversion.amount)),
+                // unrecognised positional arguments are reported by the validation,
+                // unless they form an expression such as 100USD+250EUR in PLN
+                Err(_) if args.len() == 3 && !is_conversion_expression(&args.join(" ")) => {
+                    Some((args[0].clone(), args[1].clone(), args[2].clone()))
+                }
+                Err(_) => None,
+            },
+            _ => None,
+        };
+
+        let (source, target, amount) = conversion?;
+        let options = OutputFormat::from_flags(flags, OutputFormat::Table, &[OutputFormat::Table])
+            .and_then(|(_, flags)| ConversionOptions::from_flags(&flags));
+
+        Some(match options {
+            Ok(options) => Action::new_direct_conversion(source, target, amount, options),
+            Err(message) => Action::Error { message },
+        })
     }
 
-    fn get_all_actions() -> Vec<Action> {
-        vec![
-            Action::DirectConversion {
-                source: "".to_string(),
-                target: "".to_string(),
-                amount: "".to_string(),
+    fn new_batch(args: &[String], flags: &[CliFlag]) -> Action {
+        let input = match args {
+            [] => None,
+            [input] => Some(input.clone()),
+            _ => {
+                return Action::Error {
+                    message: "batch accepts a single file, use - to read from stdin".to_string(),
+                }
+            }
+        };
+
+        let parsed = OutputFormat::from_flags(
+            flags,
+            OutputFormat::Csv,
+            &[OutputFormat::Csv, OutputFormat::Json],
+        )
+        .and_then(|(format, flags)| {
+            let options = ConversionOptions::from_flags(&flags)?;
+
+            match options.reverse {
+                true => Err("Option --receive is not supported by batch".to_string()),
+                false => Ok((format, options)),
+            }
+        });
+
+        match parsed {
+            Ok((format, options)) => Action::Batch {
+                input,
+                format,
+                options,
             },
-            Action::InteractiveMode,
-            Action::UpdateCache,
-            Action::Help,
-            Action::ListCurrencies,
-            Action::ListCurrenciesWithRates,
-            Action::Setup,
-            Action::History,
-        ]
-    }
-
-    fn new_from_single_argument(arg: &str) -> Action {
-        match arg {
-            "-i" | "interactive" => Action::InteractiveMode,
-            "-u" | "update-cache" => Action::UpdateCache,
-            "-h" | "help" => Action::Help,
-            "-l" | "list-currencies" => Action::ListCurrencies,
-            "-lr" | "list-currencies-with-rates" => Action::ListCurrenciesWithRates,
-            "-s" | "setup" => Action::Setup,
-            "-H" | "history" => Action::History,
-            _ => Action::Error {
-                message: "Invalid argument".to_string(),
+            Err(message) => Action::Error { message },
+        }
+    }
+
+    fn new_value(args: &[String], flags: &[CliFlag]) -> Action {
+        let (input, currency) = match args {
+            [input, currency] => (input.clone(), currency.to_uppercase()),
+            _ => {
+                return Action::Error {
+                    message: "Use value <file> <reporting currency>, use - to read from stdin"
+                        .to_string(),
+                }
+            }
+        };
+
+        let parsed = OutputFormat::from_flags(
+            flags,
+            OutputFormat::Table,
+            &[OutputFormat::Table, OutputFormat::Json],
+        )
+        .and_then(|(format, flags)| {
+            let options = ConversionOptions::from_flags(&flags)?;
+            let rounding_only = ConversionOptions {
+                rounding: options.rounding,
+                ..ConversionOptions::default()
+            };
+
+            match options == rounding_only {
+                true => Ok((format, options.rounding)),
+                false => Err("Only --rounding and --format are supported by value".to_string()),
+            }
+        });
+
+        match parsed {
+            Ok((format, rounding)) => Action::Value {
+                input,
+                currency,
+                format,
+                rounding,
             },
+            Err(message) => Action::Error { message },
         }
     }
 
-    fn help_print(&self) {
-        match self {
-            Action::InteractiveMode => println!(
-                "{} {} - Run in interactive mode",
-                "-i".green(),
-                "interactive".green(),
-            ),
-            Action::UpdateCache => {
-                println!("{} {} - Update cache", "-u".green(), "update-cache".green(),)
-            }
-            Action::Help => println!("{} {} - Print help", "-h".green(), "help".green()),
-            Action::ListCurrencies => println!(
-                "{} {} - List supported currencies",
-                "-l".green(),
-                "list-currencies".green(),
-            ),
-            Action::ListCurrenciesWithRates => println!(
-                "{} {} - List supported currencies with rates",
-                "-lr".green(),
-                "list-currencies-with-rates".green(),
-            ),
-            Action::Setup => println!("{} {} - Setup application", "-s".green(), "setup".green(),),
-            Action::History => {
-                println!("{} {} - Display history", "-H".green(), "history".green(),)
-            }
-            Action::DirectConversion { .. } => {
-                println!("{}", "Default use - Direct conversion".green());
-                println!("<source currency> <target currency> <amount> // Example: USD EUR 1000");
-                println!(" ");
-            }
-            Action::Error { .. } => (),
+    fn new_matrix(args: &[String], flags: &[CliFlag]) -> Action {
+        let currencies = get_matrix_currencies(&args.join(","));
+
+        let parsed = OutputFormat::from_flags(
+            flags,
+            OutputFormat::Table,
+            &[
+                OutputFormat::Table,
+                OutputFormat::Csv,
+                OutputFormat::Markdown,
+                OutputFormat::Json,
+            ],
+        );
+
+        let (format, flags) = match parsed {
+            Ok(parsed) => parsed,
+            Err(message) => return Action::Error { message },
+        };
+
+        let mut invert = false;
+        let mut change = false;
+
+        for flag in flags {
+            match flag.name.as_str() {
+                "invert" => invert = true,
+                "change" => change = true,
+                _ => {
+                    return Action::Error {
+                        message: flag.unknown(),
+                    }
+                }
+            }
+        }
+
+        Action::Matrix {
+            currencies,
+            invert,
+            change,
+            format,
         }
     }
 
-    fn new_direct_conversion(source: String, target: String, amount: String) -> Action {
-        Action::DirectConversion {
-            source,
-            target,
-            amount,
+    fn new_search(args: &[String], flags: &[CliFlag]) -> Action {
+        let query = args.join(" ");
+
+        if query.trim().is_empty() {
+            return Action::Error {
+                message: "Use search <text>, example: search dollar".to_string(),
+            };
+        }
+
+        match get_search_limit(flags) {
+            Ok(limit) => Action::Search { query, limit },
+            Err(message) => Action::Error { message },
+        }
+    }
+
+    fn new_history(args: &[String], flags: &[CliFlag]) -> Action {
+        match parse_history_command(args, flags) {
+            Ok(command) => Action::HistoryCommand { command },
+            Err(message) => Action::Error { message },
         }
     }
 
@@ -172,7 +334,9 @@ impl Action {
             Action::InteractiveMode => println!("Running in interactive mode"),
             Action::UpdateCache => println!("Updating cache"),
             Action::ListCurrencies => println!("Listing supported currencies"),
-            Action::ListCurrenciesWithRates => println!("Listing supported currencies with rates"),
+            Action::ListCurrenciesWithRates { .. } => {
+                println!("Listing supported currencies with rates")
+            }
             _ => (),
         }
     }
@@ -183,51 +347,199 @@ impl Action {
                 source,
                 target,
                 amount,
-            } => validate_args(source, target, amount).await,
+                options,
+            } => validate_args(source, target, amount, options).await,
+            Action::Expression { expression } => validate_expression(expression).await,
+            Action::Value { currency, .. } => validate_currency(currency).await,
+            Action::Matrix { currencies, .. } => validate_matrix_currencies(currencies).await,
+            Action::ListCurrenciesWithRates { options } => {
+                validate_rate_list_options(options).await
+            }
+            // the cache is reset before the update, it would be left empty
+            Action::UpdateCache if is_offline() => {
+                Err("The cache cannot be updated offline, run without --offline".to_string())
+            }
             _ => Ok(()),
         }
     }
 
-    async fn run(&self) {
+    // conversions return their entry for the history
+    async fn run(&self) -> Result<Option<CommandHistory>, String> {
         self.info_print();
 
         match self {
-            Action::Error { message } => print_error(message.as_str()),
-            Action::ListCurrencies => display_supported_currencies().await,
-            Action::ListCurrenciesWithRates => display_supported_currencies_with_rates().await,
+            Action::Error { message } => Err(message.clone()),
+            Action::ListCurrencies => display_supported_currencies().await.map(|_| None),
+            Action::ListCurrenciesWithRates { options } => {
+                display_supported_currencies_with_rates(options)
+                    .await
+                    .map(|_| None)
+            }
             Action::DirectConversion {
                 source,
                 target,
                 amount,
-            } => display_direct_conversion(source, target, amount).await,
-            Action::UpdateCache => update_cache().await,
-            Action::Setup => setup_app().await,
-            Action::History => display_history().await,
-            Action::Help => Action::print_help(),
-            Action::InteractiveMode => start_interactive_mode().await,
+                options,
+            } => display_direct_conversion(source, target, amount, options)
+                .await
+                .map(Some),
+            Action::Expression { expression } => display_expression(expression).await.map(Some),
+            Action::Batch {
+                input,
+                format,
+                options,
+            } => display_batch(input, *format, options).await.map(Some),
+            Action::Value {
+                input,
+                currency,
+                format,
+                rounding,
+            } => {
+                let rounding = rounding.unwrap_or_else(RoundingMode::from_env);
+                display_portfolio_value(input, currency, *format, rounding)
+                    .await
+                    .map(Some)
+            }
+            Action::Matrix {
+                currencies,
+                invert,
+                change,
+                format,
+            } => display_matrix(currencies, *invert, *change, *format)
+                .await
+                .map(|_| None),
+            Action::Search { query, limit } => display_search(query, *limit).await.map(|_| None),
+            Action::UpdateCache => update_cache().await.map(|_| None),
+            Action::Setup => {
+                setup_app().await;
+                Ok(None)
+            }
+            Action::History => display_history(&HistoryFilter::default())
+                .await
+                .map(|_| None),
+            Action::HistoryCommand { command } => run_history_command(command).await.map(|_| None),
+            Action::Help => {
+                print_help();
+                Ok(None)
+            }
+            Action::CommandHelp { command } => print_command_help(command).map(|_| None),
+            Action::InteractiveMode => {
+                start_interactive_mode().await;
+                Ok(None)
+            }
         }
     }
 
-    pub async fn execute(&self) {
+    // the command type an action is recorded under in the history
+    pub fn get_name(&self) -> &'static str {
+        match self {
+            Action::InteractiveMode => "interactive-mode",
+            Action::DirectConversion { .. } => "conversion",
+            Action::Expression { .. } => "expression",
+            Action::Batch { .. } => "batch",
+            Action::Value { .. } => "value",
+            Action::Matrix { .. } => "matrix",
+            Action::Search { .. } => "search",
+            Action::UpdateCache => "update-cache",
+            Action::Help | Action::CommandHelp { .. } => "help",
+            Action::Error { .. } => "invalid",
+            Action::ListCurrencies => "list-currencies",
+            Action::ListCurrenciesWithRates { .. } => "list-currencies-with-rates",
+            Action::Setup => "setup",
+            Action::History | Action::HistoryCommand { .. } => "history",
+        }
+    }
+
+    // arguments are recorded in the history as they were given
+    pub async fn execute(&self, arguments: &[String]) {
+        let started = Instant::now();
         let validation = self.validate().await;
 
-        match validation {
-            Ok(_) => self.run().await,
+        let result = match validation {
+            Ok(_) => match self.run().await {
+                Ok(history) => Ok(history),
+                Err(e) => {
+                    print_error(e.as_str());
+
+                    match self {
+                        Action::Error { .. } => Err((HistoryOutcome::InvalidArguments, e)),
+                        _ => Err((HistoryOutcome::Failed, e)),
+                    }
+                }
+            },
             Err(e) => {
                 self.info_print();
                 println!("{}", e.red());
+                Err((HistoryOutcome::ValidationError, e))
             }
+        };
+
+        let duration = started.elapsed();
+        print_verbose(&format!(
+            "{} finished in {} ms",
+            self.get_name(),
+            duration.as_millis()
+        ));
+
+        self.record_history(arguments, result, duration);
+    }
+
+    fn record_history(
+        &self,
+        arguments: &[String],
+        result: Result<Option<CommandHistory>, (HistoryOutcome, String)>,
+        duration: Duration,
+    ) {
+        // nothing was entered, e.g. an empty line in interactive mode
+        if arguments.is_empty() && matches!(self, Action::Error { .. }) {
+            return;
         }
+
+        if !HistoryRecording::from_env().records(self.get_name()) {
+            return;
+        }
+
+        let history = match result {
+            Ok(Some(history)) => history,
+            Ok(None) => CommandHistory::new(arguments.join(" "), None),
+            Err((outcome, e)) => {
+                CommandHistory::new(arguments.join(" "), None).with_outcome(outcome, Some(e))
+            }
+        };
+
+        let _ = history
+            .with_action(self.get_name(), arguments)
+            .with_duration(duration)
+            .save();
     }
 }
 
 pub async fn parse_cli_arguments(args: Vec<String>) -> Action {
-    if args.len() == 2 {
-        return Action::new_from_single_argument(&args[1]);
+    let (args, flags) = split_flags(args.get(1..).unwrap_or_default());
+    let (_, flags) = GlobalOptions::from_flags(&flags);
+
+    parse_arguments(&args, &flags)
+}
+
+fn parse_arguments(args: &[String], flags: &[CliFlag]) -> Action {
+    if let Some(action) = parse_command(args, flags) {
+        return action;
+    }
+
+    // the legacy form without a command, USD EUR 100
+    if let Some(action) = Action::new_conversion(args, flags) {
+        return action;
+    }
+
+    let expression = args.join(" ");
+    if flags.is_empty() && is_conversion_expression(&expression) {
+        return Action::Expression { expression };
     }
 
-    if args.len() == 4 {
-        return Action::new_direct_conversion(args[1].clone(), args[2].clone(), args[3].clone());
+    if args.len() == 1 {
+        return Action::Error {
+            message: "Invalid argument".to_string(),
+        };
     }
 
     Action::Error {
@@ -236,6 +548,12 @@ pub async fn parse_cli_arguments(args: Vec<String>) -> Action {
     }
 }
 
+pub fn parse_global_options(args: &[String]) -> GlobalOptions {
+    let (_, flags) = split_flags(args.get(1..).unwrap_or_default());
+
+    GlobalOptions::from_flags(&flags).0
+}
+
 pub async fn missing_config() {
     println!(
         "{} {}",
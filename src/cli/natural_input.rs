@@ -0,0 +1,179 @@
+use rust_decimal::Decimal;
+
+use crate::{
+    currency::Currency,
+    validation::amount::{split_leading_suffix, AmountParser},
+};
+
+const CONNECTORS: [&str; 6] = ["to", "in", "into", "=", "->", "→"];
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct NaturalConversion {
+    pub source: String,
+    pub target: String,
+    pub amount: String,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+enum Item {
+    Amount(String),
+    Currencies(Vec<String>),
+    Pair(String, String),
+    Connector,
+}
+
+// accepts "USD EUR 100", "100 usd to eur", "100USD EUR", "$100 in €", "EUR/USD 100" and "1.5k JPY to USD"
+pub fn parse_natural_conversion(input: &str) -> Result<NaturalConversion, String> {
+    let mut items: Vec<Item> = vec![];
+
+    for word in input.split_whitespace() {
+        items.append(&mut parse_word(word)?);
+    }
+
+    let connectors = items
+        .iter()
+        .filter(|item| **item == Item::Connector)
+        .count();
+    let misplaced_connector =
+        items.first() == Some(&Item::Connector) || items.last() == Some(&Item::Connector);
+
+    if connectors > 1 || misplaced_connector {
+        return Err(format!("Could not understand '{}'", input));
+    }
+
+    items.retain(|item| *item != Item::Connector);
+
+    match items.as_slice() {
+        [Item::Currencies(source), Item::Currencies(target), Item::Amount(amount)]
+        | [Item::Amount(amount), Item::Currencies(source), Item::Currencies(target)]
+        | [Item::Currencies(source), Item::Amount(amount), Item::Currencies(target)] => {
+            NaturalConversion::new(source, target, amount)
+        }
+        [Item::Pair(source, target), Item::Amount(amount)]
+        | [Item::Amount(amount), Item::Pair(source, target)] => Ok(NaturalConversion {
+            source: source.clone(),
+            target: target.clone(),
+            amount: amount.clone(),
+        }),
+        _ => Err(format!("Could not understand '{}'", input)),
+    }
+}
+
+impl NaturalConversion {
+    fn new(
+        source: &[String],
+        target: &[String],
+        amount: &str,
+    ) -> Result<NaturalConversion, String> {
+        match source {
+            [source] => Ok(NaturalConversion {
+                source: source.clone(),
+                target: target.join(","),
+                amount: amount.to_string(),
+            }),
+            _ => Err("Only one source currency can be converted at a time".to_string()),
+        }
+    }
+}
+
+fn parse_word(word: &str) -> Result<Vec<Item>, String> {
+    if CONNECTORS.contains(&word.to_lowercase().as_str()) {
+        return Ok(vec![Item::Connector]);
+    }
+
+    let first_digit = word.find(|char: char| char.is_ascii_digit());
+    let last_digit = word.rfind(|char: char| char.is_ascii_digit());
+
+    let (first_digit, last_digit) = match (first_digit, last_digit) {
+        (Some(first_digit), Some(last_digit)) => (first_digit, last_digit),
+        _ => return parse_currencies(word).map(|item| vec![item]),
+    };
+
+    let prefix = &word[..first_digit];
+    let number = &word[first_digit..=last_digit];
+    let rest = &word[last_digit + 1..];
+
+    if number.contains(|char: char| !(char.is_ascii_digit() || char == '.' || char == ',')) {
+        return Err(format!("{} is not a valid amount", word));
+    }
+
+    let mut items: Vec<Item> = vec![];
+
+    if !prefix.is_empty() {
+        items.push(Item::Currencies(vec![resolve_currency(prefix)?]));
+    }
+
+    match split_amount_suffix(rest) {
+        Some((multiplier, currency)) => {
+            items.insert(0, Item::Amount(apply_multiplier(number, multiplier)?));
+
+            if !currency.is_empty() {
+                items.push(Item::Currencies(vec![resolve_currency(currency)?]));
+            }
+        }
+        None => {
+            items.insert(0, Item::Amount(number.to_string()));
+
+            if !rest.is_empty() {
+                items.push(Item::Currencies(vec![resolve_currency(rest)?]));
+            }
+        }
+    }
+
+    Ok(items)
+}
+
+fn parse_currencies(word: &str) -> Result<Item, String> {
+    if let Some((source, target)) = word.split_once('/') {
+        return Ok(Item::Pair(
+            resolve_currency(source)?,
+            resolve_currency(target)?,
+        ));
+    }
+
+    let currencies = word
+        .split(',')
+        .filter(|code| !code.is_empty())
+        .map(resolve_currency)
+        .collect::<Result<Vec<String>, String>>()?;
+
+    Ok(Item::Currencies(currencies))
+}
+
+// "k" in "1.5k" is a multiplier, but "MXN" in "100MXN" is a currency
+fn split_amount_suffix(rest: &str) -> Option<(i64, &str)> {
+    let (multiplier, currency) = split_leading_suffix(rest)?;
+
+    if currency.is_empty() {
+        return Some((multiplier, ""));
+    }
+
+    if resolve_currency(rest).is_ok() {
+        return None;
+    }
+
+    match resolve_currency(currency).is_ok() {
+        true => Some((multiplier, currency)),
+        false => None,
+    }
+}
+
+fn apply_multiplier(number: &str, multiplier: i64) -> Result<String, String> {
+    let amount = AmountParser::from_env().parse(number)?;
+
+    match amount.checked_mul(Decimal::from(multiplier)) {
+        Some(amount) => Ok(amount.normalize().to_string()),
+        None => Err(format!("{} is too large", number)),
+    }
+}
+
+fn resolve_currency(text: &str) -> Result<String, String> {
+    if text.len() == 3 && text.chars().all(|char| char.is_ascii_alphabetic()) {
+        return Ok(text.to_uppercase());
+    }
+
+    match Currency::new_from_symbol(text) {
+        Some(currency) => Ok(currency.get_code().clone()),
+        None => Err(format!("{} is not a valid currency", text)),
+    }
+}
@@ -0,0 +1,258 @@
+use std::{fmt, str::FromStr};
+
+use rust_decimal::Decimal;
+
+use crate::{
+    api::{is_offline, set_offline},
+    conversion::{
+        converter::{FeeCurrency, FeeSchedule},
+        rounding::RoundingMode,
+    },
+    error::{is_verbose, set_verbose},
+};
+
+use super::parser::is_switch;
+
+#[derive(Debug, PartialEq, Clone, Copy, Default)]
+pub struct GlobalOptions {
+    pub no_color: bool,
+    pub offline: bool,
+    pub verbose: bool,
+}
+
+impl GlobalOptions {
+    // takes the global switches, accepted by every command, out of the flags
+    // the remaining flags are returned
+    pub fn from_flags(flags: &[CliFlag]) -> (GlobalOptions, Vec<CliFlag>) {
+        let mut options = GlobalOptions::default();
+        let mut remaining: Vec<CliFlag> = vec![];
+
+        for flag in flags {
+            match flag.name.as_str() {
+                "no-color" => options.no_color = true,
+                "offline" => options.offline = true,
+                "verbose" => options.verbose = true,
+                _ => remaining.push(flag.clone()),
+            }
+        }
+
+        (options, remaining)
+    }
+
+    // the options in effect, e.g. those of the interactive mode session
+    pub fn current() -> GlobalOptions {
+        GlobalOptions {
+            no_color: !colored::control::SHOULD_COLORIZE.should_colorize(),
+            offline: is_offline(),
+            verbose: is_verbose(),
+        }
+    }
+
+    pub fn merge(&self, other: &GlobalOptions) -> GlobalOptions {
+        GlobalOptions {
+            no_color: self.no_color || other.no_color,
+            offline: self.offline || other.offline,
+            verbose: self.verbose || other.verbose,
+        }
+    }
+
+    pub fn apply(&self) {
+        match self.no_color {
+            true => colored::control::set_override(false),
+            false => colored::control::unset_override(),
+        }
+
+        set_offline(self.offline);
+        set_verbose(self.verbose);
+    }
+}
+
+#[derive(Debug, PartialEq, Clone, Default)]
+pub struct ConversionOptions {
+    pub rounding: Option<RoundingMode>,
+    pub spread_percent: Option<Decimal>,
+    pub fixed_fee: Option<Decimal>,
+    pub fixed_fee_currency: Option<FeeCurrency>,
+    pub minimum_fee: Option<Decimal>,
+    pub reverse: bool,
+}
+
+impl ConversionOptions {
+    pub fn from_flags(flags: &[CliFlag]) -> Result<ConversionOptions, String> {
+        let mut options = ConversionOptions::default();
+
+        for flag in flags {
+            match flag.name.as_str() {
+                "rounding" => {
+                    let mode = flag.get_value()?.parse::<RoundingMode>()?;
+                    options.rounding = Some(mode);
+                }
+                "spread" => options.spread_percent = Some(flag.get_decimal_value()?),
+                "fee" => options.fixed_fee = Some(flag.get_decimal_value()?),
+                "fee-currency" => {
+                    let fee_currency = flag.get_value()?.parse::<FeeCurrency>()?;
+                    options.fixed_fee_currency = Some(fee_currency);
+                }
+                "min-fee" => options.minimum_fee = Some(flag.get_decimal_value()?),
+                "receive" => options.reverse = true,
+                _ => return Err(flag.unknown()),
+            }
+        }
+
+        Ok(options)
+    }
+
+    pub fn get_rounding(&self) -> RoundingMode {
+        match self.rounding {
+            Some(rounding) => rounding,
+            None => RoundingMode::from_env(),
+        }
+    }
+
+    pub fn get_fee_schedule(&self) -> FeeSchedule {
+        let defaults = FeeSchedule::from_env();
+
+        FeeSchedule {
+            spread_percent: self.spread_percent.unwrap_or(defaults.spread_percent),
+            fixed_fee: self.fixed_fee.unwrap_or(defaults.fixed_fee),
+            fixed_fee_currency: self
+                .fixed_fee_currency
+                .unwrap_or(defaults.fixed_fee_currency),
+            minimum_fee: self.minimum_fee.unwrap_or(defaults.minimum_fee),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Clone, Copy, Default)]
+pub enum OutputFormat {
+    #[default]
+    Table,
+    Csv,
+    Json,
+    Markdown,
+}
+
+impl OutputFormat {
+    // takes the --format flag out of the flags, the remaining flags are returned
+    pub fn from_flags(
+        flags: &[CliFlag],
+        default: OutputFormat,
+        supported: &[OutputFormat],
+    ) -> Result<(OutputFormat, Vec<CliFlag>), String> {
+        let mut format = default;
+        let mut remaining: Vec<CliFlag> = vec![];
+
+        for flag in flags {
+            match flag.name.as_str() {
+                "format" => format = flag.get_value()?.parse::<OutputFormat>()?,
+                _ => remaining.push(flag.clone()),
+            }
+        }
+
+        if !supported.contains(&format) {
+            let supported = supported
+                .iter()
+                .map(|format| format.to_string())
+                .collect::<Vec<String>>()
+                .join(", ");
+
+            return Err(format!(
+                "Output format {} is not supported here, use one of: {}",
+                format, supported
+            ));
+        }
+
+        Ok((format, remaining))
+    }
+}
+
+impl FromStr for OutputFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "table" | "text" => Ok(OutputFormat::Table),
+            "csv" => Ok(OutputFormat::Csv),
+            "json" => Ok(OutputFormat::Json),
+            "markdown" | "md" => Ok(OutputFormat::Markdown),
+            _ => Err(format!(
+                "{} is not a valid output format, use one of: table, csv, json, markdown",
+                s
+            )),
+        }
+    }
+}
+
+impl fmt::Display for OutputFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let format = match self {
+            OutputFormat::Table => "table",
+            OutputFormat::Csv => "csv",
+            OutputFormat::Json => "json",
+            OutputFormat::Markdown => "markdown",
+        };
+
+        write!(f, "{}", format)
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct CliFlag {
+    pub name: String,
+    pub value: Option<String>,
+}
+
+impl CliFlag {
+    pub fn get_value(&self) -> Result<&String, String> {
+        match self.value.as_ref() {
+            Some(value) => Ok(value),
+            None => Err(format!("Option --{} requires a value", self.name)),
+        }
+    }
+
+    pub fn get_decimal_value(&self) -> Result<Decimal, String> {
+        let value = self.get_value()?;
+
+        Decimal::from_str(value)
+            .map_err(|_| format!("Option --{}: {} is not a valid number", self.name, value))
+    }
+
+    pub fn unknown(&self) -> String {
+        format!("Unknown option --{}", self.name)
+    }
+}
+
+pub fn split_flags(args: &[String]) -> (Vec<String>, Vec<CliFlag>) {
+    let mut positionals: Vec<String> = vec![];
+    let mut flags: Vec<CliFlag> = vec![];
+    let mut args = args.iter().peekable();
+
+    while let Some(arg) = args.next() {
+        match arg.strip_prefix("--") {
+            Some(flag) => {
+                if let Some((name, value)) = flag.split_once('=') {
+                    flags.push(CliFlag {
+                        name: name.to_string(),
+                        value: Some(value.to_string()),
+                    });
+                    continue;
+                }
+
+                let value = match args.peek() {
+                    Some(next) if !next.starts_with("--") && !is_switch(flag) => {
+                        args.next().cloned()
+                    }
+                    _ => None,
+                };
+
+                flags.push(CliFlag {
+                    name: flag.to_string(),
+                    value,
+                });
+            }
+            None => positionals.push(arg.clone()),
+        }
+    }
+
+    (positionals, flags)
+}
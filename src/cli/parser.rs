@@ -0,0 +1,699 @@
+use colored::Colorize;
+
+use super::{
+    list_currencies_with_rates::RateListOptions,
+    options::{CliFlag, OutputFormat},
+    Action,
+};
+
+type Builder = fn(&[String], &[CliFlag]) -> Action;
+
+pub struct FlagSpec {
+    pub name: &'static str,
+    pub value: Option<&'static str>,
+    pub description: &'static str,
+}
+
+// commands are described once, the parser and the help are derived from the description
+pub struct CommandSpec {
+    pub name: &'static str,
+    // older names, still accepted
+    pub aliases: &'static [&'static str],
+    pub usage: &'static str,
+    pub description: &'static str,
+    pub flags: &'static [FlagSpec],
+    // the first format is the default, no formats means only the table output
+    pub formats: &'static [OutputFormat],
+    pub subcommands: &'static [CommandSpec],
+    pub examples: &'static [&'static str],
+    // subcommands without a builder are built by their command
+    build: Option<Builder>,
+}
+
+const fn flag(name: &'static str, value: &'static str, description: &'static str) -> FlagSpec {
+    FlagSpec {
+        name,
+        value: Some(value),
+        description,
+    }
+}
+
+const fn switch(name: &'static str, description: &'static str) -> FlagSpec {
+    FlagSpec {
+        name,
+        value: None,
+        description,
+    }
+}
+
+const ROUNDING_FLAG: FlagSpec = flag(
+    "rounding",
+    "<mode>",
+    "Rounding of the result: half-up, half-even, floor, ceiling, truncate",
+);
+const SPREAD_FLAG: FlagSpec = flag("spread", "<percent>", "Spread applied to the exchange rate");
+const FEE_FLAG: FlagSpec = flag("fee", "<amount>", "Fixed fee");
+const FEE_CURRENCY_FLAG: FlagSpec = flag(
+    "fee-currency",
+    "<source|target>",
+    "Currency of the fixed fee",
+);
+const MIN_FEE_FLAG: FlagSpec = flag("min-fee", "<amount>", "Minimum fee in the target currency");
+
+const HISTORY_FILTER_FLAGS: [FlagSpec; 9] = [
+    flag(
+        "source",
+        "<currency>",
+        "Only show conversions from the currency",
+    ),
+    flag(
+        "target",
+        "<currency>",
+        "Only show conversions into the currency",
+    ),
+    flag(
+        "pair",
+        "<source/target>",
+        "Only show conversions of the pair",
+    ),
+    flag("since", "<YYYY-MM-DD>", "Only show entries from the day on"),
+    flag("until", "<YYYY-MM-DD>", "Only show entries up to the day"),
+    flag(
+        "min-amount",
+        "<amount>",
+        "Only show conversions of at least this source amount",
+    ),
+    flag(
+        "max-amount",
+        "<amount>",
+        "Only show conversions of at most this source amount",
+    ),
+    flag("search", "<text>", "Only show entries containing the text"),
+    flag("last", "<n>", "Only show the last n matching entries"),
+];
+
+pub const GLOBAL_FLAGS: [FlagSpec; 5] = [
+    flag(
+        "format",
+        "<format>",
+        "Output format, for the commands that support several",
+    ),
+    switch("no-color", "Print without colors"),
+    switch(
+        "offline",
+        "Only use cached currencies and rates, the API is never called",
+    ),
+    switch(
+        "verbose",
+        "Print cache use, API requests and timing to stderr",
+    ),
+    switch("help", "Print the help of a command"),
+];
+
+pub const COMMANDS: [CommandSpec; 12] = [
+    CommandSpec {
+        name: "convert",
+        aliases: &[],
+        usage: "<source> <target>[,<target>...] <amount>",
+        description: "Convert an amount, the command name can be left out",
+        flags: &[
+            flag(
+                "receive",
+                "<amount>",
+                "Amount of source currency needed to receive the amount, instead of <amount>",
+            ),
+            ROUNDING_FLAG,
+            SPREAD_FLAG,
+            FEE_FLAG,
+            FEE_CURRENCY_FLAG,
+            MIN_FEE_FLAG,
+        ],
+        formats: &[],
+        subcommands: &[],
+        examples: &["convert USD EUR,GBP 100", "USD EUR --receive 500"],
+        build: Some(build_convert),
+    },
+    CommandSpec {
+        name: "batch",
+        aliases: &[],
+        usage: "[file]",
+        description: "Convert the rows (source, target, amount, optional date) of a CSV file, use - or no file to read from stdin",
+        flags: &[
+            ROUNDING_FLAG,
+            SPREAD_FLAG,
+            FEE_FLAG,
+            FEE_CURRENCY_FLAG,
+            MIN_FEE_FLAG,
+        ],
+        formats: &[OutputFormat::Csv, OutputFormat::Json],
+        subcommands: &[],
+        examples: &["batch expenses.csv"],
+        build: Some(Action::new_batch),
+    },
+    CommandSpec {
+        name: "value",
+        aliases: &[],
+        usage: "<file> <reporting currency>",
+        description: "Value the holdings (currency, amount, optional label) of a CSV file, use - to read from stdin",
+        flags: &[ROUNDING_FLAG],
+        formats: &[OutputFormat::Table, OutputFormat::Json],
+        subcommands: &[],
+        examples: &["value holdings.csv USD"],
+        build: Some(Action::new_value),
+    },
+    CommandSpec {
+        name: "matrix",
+        aliases: &[],
+        usage: "<currency>,<currency>...",
+        description: "Table of the cross rates between the currencies",
+        flags: &[
+            switch("invert", "Show the inverted rates"),
+            switch(
+                "change",
+                "Show the percentage change since the last rate snapshot",
+            ),
+        ],
+        formats: &[
+            OutputFormat::Table,
+            OutputFormat::Csv,
+            OutputFormat::Markdown,
+            OutputFormat::Json,
+        ],
+        subcommands: &[],
+        examples: &["matrix USD,EUR,GBP,CHF,JPY"],
+        build: Some(Action::new_matrix),
+    },
+    CommandSpec {
+        name: "search",
+        aliases: &[],
+        usage: "<text>",
+        description: "Find currencies by code, name, alias, symbol or country",
+        flags: &[flag(
+            "limit",
+            "<n>",
+            "Number of candidates to show, 10 by default",
+        )],
+        formats: &[],
+        subcommands: &[],
+        examples: &["search quid", "search Switzerland"],
+        build: Some(Action::new_search),
+    },
+    CommandSpec {
+        name: "list",
+        aliases: &["list-currencies", "-l"],
+        usage: "",
+        description: "List supported currencies",
+        flags: &[],
+        formats: &[],
+        subcommands: &[],
+        examples: &[],
+        build: Some(build_list),
+    },
+    CommandSpec {
+        name: "rates",
+        aliases: &["list-currencies-with-rates", "-lr"],
+        usage: "",
+        description: "List supported currencies with rates",
+        flags: &[
+            flag(
+                "base",
+                "<currency>,<currency>...",
+                "Only list the rates of these base currencies",
+            ),
+            flag(
+                "targets",
+                "<currency>,<currency>...",
+                "Only list the rates into these currencies",
+            ),
+            flag(
+                "sort",
+                "<code|rate>",
+                "Order of the rates of each base, code by default",
+            ),
+            flag("top", "<n>", "Only list the first n rates of each base"),
+            flag("page", "<n>", "Show one page of the rates"),
+            flag("page-size", "<n>", "Rates per page, 50 by default"),
+        ],
+        formats: &[],
+        subcommands: &[],
+        examples: &["rates --base USD,EUR --targets GBP,JPY --sort rate"],
+        build: Some(build_rates),
+    },
+    CommandSpec {
+        name: "history",
+        aliases: &["-H"],
+        usage: "",
+        description: "Display the history of commands",
+        flags: &HISTORY_FILTER_FLAGS,
+        formats: &[],
+        subcommands: &[
+            CommandSpec {
+                name: "replay",
+                aliases: &[],
+                usage: "[id]",
+                description: "Run a stored conversion again at today's rates, without an id every entry matching the filter options is replayed",
+                flags: &[],
+                formats: &[],
+                subcommands: &[],
+                examples: &["history replay 12"],
+                build: None,
+            },
+            CommandSpec {
+                name: "export",
+                aliases: &[],
+                usage: "",
+                description: "Export the entries matching the filter options",
+                flags: &[flag("output", "<file>", "File to write, stdout by default")],
+                formats: &[OutputFormat::Csv, OutputFormat::Json, OutputFormat::Markdown],
+                subcommands: &[],
+                examples: &["history export --output history.csv"],
+                build: None,
+            },
+            CommandSpec {
+                name: "stats",
+                aliases: &[],
+                usage: "",
+                description: "Most used pairs, volume per currency, conversions per day and week and average rates of the entries matching the filter options",
+                flags: &[],
+                formats: &[OutputFormat::Table, OutputFormat::Json],
+                subcommands: &[],
+                examples: &[],
+                build: None,
+            },
+            CommandSpec {
+                name: "delete",
+                aliases: &[],
+                usage: "<id>",
+                description: "Delete an entry",
+                flags: &[],
+                formats: &[],
+                subcommands: &[],
+                examples: &[],
+                build: None,
+            },
+            CommandSpec {
+                name: "clear",
+                aliases: &[],
+                usage: "",
+                description: "Delete every entry except pinned ones",
+                flags: &[flag(
+                    "before",
+                    "<YYYY-MM-DD>",
+                    "Only delete the entries before the day",
+                )],
+                formats: &[],
+                subcommands: &[],
+                examples: &[],
+                build: None,
+            },
+            CommandSpec {
+                name: "pin",
+                aliases: &[],
+                usage: "<id>",
+                description: "Keep an entry regardless of the history retention",
+                flags: &[],
+                formats: &[],
+                subcommands: &[],
+                examples: &[],
+                build: None,
+            },
+            CommandSpec {
+                name: "unpin",
+                aliases: &[],
+                usage: "<id>",
+                description: "Let the history retention remove an entry again",
+                flags: &[],
+                formats: &[],
+                subcommands: &[],
+                examples: &[],
+                build: None,
+            },
+        ],
+        examples: &["history --pair USD/EUR --since 2024-04-01 --last 10"],
+        build: Some(build_history),
+    },
+    CommandSpec {
+        name: "cache",
+        aliases: &[],
+        usage: "",
+        description: "Manage the cache",
+        flags: &[],
+        formats: &[],
+        subcommands: &[CommandSpec {
+            name: "update",
+            aliases: &["update-cache", "-u"],
+            usage: "",
+            description: "Update the cache with the latest currencies and exchange rates",
+            flags: &[],
+            formats: &[],
+            subcommands: &[],
+            examples: &[],
+            build: Some(build_cache_update),
+        }],
+        examples: &[],
+        build: None,
+    },
+    CommandSpec {
+        name: "config",
+        aliases: &[],
+        usage: "",
+        description: "Manage the configuration",
+        flags: &[],
+        formats: &[],
+        subcommands: &[CommandSpec {
+            name: "setup",
+            aliases: &["setup", "-s"],
+            usage: "",
+            description: "Set up the application",
+            flags: &[],
+            formats: &[],
+            subcommands: &[],
+            examples: &[],
+            build: Some(build_setup),
+        }],
+        examples: &[],
+        build: None,
+    },
+    CommandSpec {
+        name: "interactive",
+        aliases: &["interactive-mode", "-i"],
+        usage: "",
+        description: "Run in interactive mode",
+        flags: &[],
+        formats: &[],
+        subcommands: &[],
+        examples: &[],
+        build: Some(build_interactive),
+    },
+    CommandSpec {
+        name: "help",
+        aliases: &["-h"],
+        usage: "[command]",
+        description: "Print help, of a single command when it is given",
+        flags: &[],
+        formats: &[],
+        subcommands: &[],
+        examples: &["help history"],
+        build: Some(build_help),
+    },
+];
+
+impl CommandSpec {
+    fn matches(&self, name: &str) -> bool {
+        self.name == name || self.aliases.contains(&name)
+    }
+
+    fn find_subcommand(&self, name: &str) -> Option<&'static CommandSpec> {
+        find_command_spec(self.name)?
+            .subcommands
+            .iter()
+            .find(|subcommand| subcommand.name == name)
+    }
+}
+
+fn find_command_spec(name: &str) -> Option<&'static CommandSpec> {
+    COMMANDS.iter().find(|command| command.matches(name))
+}
+
+// a command by its name, or a subcommand by one of its older names, e.g. update-cache
+fn find_command(name: &str) -> Option<(&'static CommandSpec, Option<&'static CommandSpec>)> {
+    if let Some(command) = find_command_spec(name) {
+        return Some((command, None));
+    }
+
+    COMMANDS.iter().find_map(|command| {
+        command
+            .subcommands
+            .iter()
+            .find(|subcommand| subcommand.aliases.contains(&name))
+            .map(|subcommand| (command, Some(subcommand)))
+    })
+}
+
+// flags described without a value never take the next argument, e.g. --invert USD
+pub fn is_switch(name: &str) -> bool {
+    fn has_switch(flags: &[FlagSpec], name: &str) -> bool {
+        flags
+            .iter()
+            .any(|flag| flag.name == name && flag.value.is_none())
+    }
+
+    fn command_has_switch(command: &CommandSpec, name: &str) -> bool {
+        has_switch(command.flags, name)
+            || command
+                .subcommands
+                .iter()
+                .any(|subcommand| command_has_switch(subcommand, name))
+    }
+
+    has_switch(&GLOBAL_FLAGS, name)
+        || COMMANDS
+            .iter()
+            .any(|command| command_has_switch(command, name))
+}
+
+// None when the arguments don't start with a command, e.g. USD EUR 100
+pub fn parse_command(args: &[String], flags: &[CliFlag]) -> Option<Action> {
+    let (command, mut subcommand) = find_command(args.first()?)?;
+    let mut subcommand_args = &args[1..];
+
+    if subcommand.is_none() {
+        subcommand = args.get(1).and_then(|name| command.find_subcommand(name));
+        if subcommand.is_some() {
+            subcommand_args = &args[2..];
+        }
+    }
+
+    if flags.iter().any(|flag| flag.name == "help") {
+        let name = match subcommand {
+            Some(subcommand) => format!("{} {}", command.name, subcommand.name),
+            None => command.name.to_string(),
+        };
+        return Some(Action::CommandHelp { command: name });
+    }
+
+    let spec = subcommand.unwrap_or(command);
+    let flags = match spec.formats.is_empty() {
+        // --format is global, commands with a single output only accept the table
+        true => {
+            match OutputFormat::from_flags(flags, OutputFormat::Table, &[OutputFormat::Table]) {
+                Ok((_, flags)) => flags,
+                Err(message) => return Some(Action::Error { message }),
+            }
+        }
+        false => flags.to_vec(),
+    };
+
+    let action = match (
+        subcommand.and_then(|subcommand| subcommand.build),
+        command.build,
+    ) {
+        (Some(build), _) => build(subcommand_args, &flags),
+        (None, Some(build)) => build(&args[1..], &flags),
+        (None, None) => Action::Error {
+            message: format!(
+                "Use {} <{}>",
+                command.name,
+                command
+                    .subcommands
+                    .iter()
+                    .map(|subcommand| subcommand.name)
+                    .collect::<Vec<&str>>()
+                    .join("|")
+            ),
+        },
+    };
+
+    Some(action)
+}
+
+fn without_arguments(args: &[String], flags: &[CliFlag], action: Action) -> Action {
+    if let Some(arg) = args.first() {
+        return Action::Error {
+            message: format!(
+                "Unexpected argument {}, use -h to see the list of available commands",
+                arg
+            ),
+        };
+    }
+
+    match flags.first() {
+        Some(flag) => Action::Error {
+            message: flag.unknown(),
+        },
+        None => action,
+    }
+}
+
+fn build_convert(args: &[String], flags: &[CliFlag]) -> Action {
+    Action::new_conversion(args, flags).unwrap_or_else(|| Action::Error {
+        message: "Use convert <source> <target> <amount>, example: convert USD EUR 100".to_string(),
+    })
+}
+
+fn build_list(args: &[String], flags: &[CliFlag]) -> Action {
+    without_arguments(args, flags, Action::ListCurrencies)
+}
+
+fn build_rates(args: &[String], flags: &[CliFlag]) -> Action {
+    match RateListOptions::from_flags(flags) {
+        Ok(options) => without_arguments(args, &[], Action::ListCurrenciesWithRates { options }),
+        Err(message) => Action::Error { message },
+    }
+}
+
+fn build_history(args: &[String], flags: &[CliFlag]) -> Action {
+    match args.is_empty() && flags.is_empty() {
+        true => Action::History,
+        false => Action::new_history(args, flags),
+    }
+}
+
+fn build_cache_update(args: &[String], flags: &[CliFlag]) -> Action {
+    without_arguments(args, flags, Action::UpdateCache)
+}
+
+fn build_setup(args: &[String], flags: &[CliFlag]) -> Action {
+    without_arguments(args, flags, Action::Setup)
+}
+
+fn build_interactive(args: &[String], flags: &[CliFlag]) -> Action {
+    without_arguments(args, flags, Action::InteractiveMode)
+}
+
+fn build_help(args: &[String], flags: &[CliFlag]) -> Action {
+    match args {
+        [] => without_arguments(args, flags, Action::Help),
+        [command, subcommand @ ..] => match find_command(command) {
+            Some((command, None)) => Action::CommandHelp {
+                command: [&[command.name.to_string()], subcommand].concat().join(" "),
+            },
+            Some((command, Some(subcommand))) => Action::CommandHelp {
+                command: format!("{} {}", command.name, subcommand.name),
+            },
+            None => Action::Error {
+                message: format!("Unknown command {}", command),
+            },
+        },
+    }
+}
+
+pub fn print_help() {
+    println!("{}", "Default use - Direct conversion".green());
+    println!("<source currency> <target currency> <amount> // Example: USD EUR 1000");
+    println!(
+        "<source currency> <target currency>,<target currency>... <amount> // Example: USD EUR,GBP,JPY 1000"
+    );
+    println!(
+        "\"<expression> in <target currency>\" // Example: \"100 USD + 250 EUR - 30 GBP in PLN\", \"(1200 CHF / 12) in USD\""
+    );
+    println!(" ");
+
+    println!("{}", "Commands".green());
+    for command in COMMANDS.iter() {
+        print_command(command, None);
+    }
+    println!(" ");
+
+    println!("{}", "Global options".green());
+    for flag in GLOBAL_FLAGS.iter() {
+        print_flag(flag, "    ");
+    }
+}
+
+pub fn print_command_help(name: &str) -> Result<(), String> {
+    let mut names = name.split_whitespace();
+    let command = names
+        .next()
+        .and_then(find_command_spec)
+        .ok_or_else(|| format!("Unknown command {}", name))?;
+
+    match names.next() {
+        None => print_command(command, None),
+        Some(subcommand_name) => match command.find_subcommand(subcommand_name) {
+            Some(subcommand) => {
+                print_command(subcommand, Some(command.name));
+                print_flags(command.flags, "    ");
+            }
+            None => return Err(format!("Unknown command {}", name)),
+        },
+    }
+
+    Ok(())
+}
+
+fn print_command(command: &CommandSpec, parent: Option<&str>) {
+    let indent = match parent {
+        Some(_) => "  ",
+        None => "",
+    };
+    let name = match parent {
+        Some(parent) => format!("{} {}", parent, command.name),
+        None => command.name.to_string(),
+    };
+    let usage = match command.usage {
+        "" => "".to_string(),
+        usage => format!(" {}", usage),
+    };
+    let aliases = match command.aliases.is_empty() {
+        true => "".to_string(),
+        false => format!(" ({})", command.aliases.join(", ")),
+    };
+
+    println!(
+        "{}{}{}{} - {}",
+        indent,
+        name.green(),
+        usage,
+        aliases,
+        command.description
+    );
+
+    let flag_indent = format!("{}    ", indent);
+    print_flags(command.flags, &flag_indent);
+
+    if let Some(default) = command.formats.first() {
+        let formats: Vec<String> = command
+            .formats
+            .iter()
+            .map(|format| format.to_string())
+            .collect();
+
+        println!(
+            "{}{} <{}> - Output format, {} by default",
+            flag_indent,
+            "--format".green(),
+            formats.join("|"),
+            default
+        );
+    }
+
+    for example in command.examples {
+        println!("{}// Example: {}", flag_indent, example);
+    }
+
+    for subcommand in command.subcommands {
+        print_command(subcommand, Some(&name));
+    }
+}
+
+fn print_flags(flags: &[FlagSpec], indent: &str) {
+    for flag in flags {
+        print_flag(flag, indent);
+    }
+}
+
+fn print_flag(flag: &FlagSpec, indent: &str) {
+    let name = format!("--{}", flag.name);
+
+    match flag.value {
+        Some(value) => println!(
+            "{}{} {} - {}",
+            indent,
+            name.green(),
+            value,
+            flag.description
+        ),
+        None => println!("{}{} - {}", indent, name.green(), flag.description),
+    }
+}
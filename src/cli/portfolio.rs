@@ -0,0 +1,257 @@
+use colored::Colorize;
+use rust_decimal::Decimal;
+use serde::Serialize;
+
+use crate::{
+    conversion::rounding::RoundingMode,
+    currency::{get_rate_table, history::CommandHistory, Currency, RateTable},
+    error::print_warning,
+    validation::amount::AmountParser,
+};
+
+use super::{
+    batch::{open_input, read_csv_rows, BatchRow},
+    options::OutputFormat,
+    table::Table,
+};
+
+const SHARE_DECIMAL_PLACES: u32 = 2;
+// cross rates are exact quotients, only their display is shortened
+const RATE_DECIMAL_PLACES: u32 = 6;
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct HoldingValue {
+    pub line: u64,
+    pub label: Option<String>,
+    pub currency: String,
+    pub amount: String,
+    pub rate: Option<String>,
+    pub value: Option<String>,
+    pub share_percent: Option<String>,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct PortfolioValuation {
+    pub reporting_currency: String,
+    pub total: String,
+    pub rate_timestamp: Option<String>,
+    pub holdings: Vec<HoldingValue>,
+}
+
+impl PortfolioValuation {
+    pub fn get_failed_holdings(&self) -> usize {
+        self.holdings
+            .iter()
+            .filter(|holding| holding.error.is_some())
+            .count()
+    }
+}
+
+// rows hold the currency, the amount and an optional label
+pub fn value_holdings(
+    rows: &[BatchRow],
+    rate_table: &RateTable,
+    rounding: RoundingMode,
+) -> PortfolioValuation {
+    let reporting_currency = rate_table.get_base();
+    let parser = AmountParser::from_env();
+
+    let values: Vec<(HoldingValue, Option<Decimal>)> = rows
+        .iter()
+        .map(|row| {
+            let currency = row.get_column(0).to_uppercase();
+            let label = match row.get_column(2) {
+                "" => None,
+                label => Some(label.to_string()),
+            };
+
+            let mut holding = HoldingValue {
+                line: row.line,
+                label,
+                currency: currency.clone(),
+                amount: row.get_column(1).to_string(),
+                rate: None,
+                value: None,
+                share_percent: None,
+                error: None,
+            };
+
+            let value = value_holding(row, &currency, rate_table, &parser);
+
+            match value {
+                Ok((rate, value)) => {
+                    holding.rate = Some(
+                        RoundingMode::HalfEven
+                            .round(rate, RATE_DECIMAL_PLACES)
+                            .normalize()
+                            .to_string(),
+                    );
+                    holding.value = Some(
+                        reporting_currency
+                            .format_amount(reporting_currency.round_amount_with(value, rounding)),
+                    );
+
+                    (holding, Some(value))
+                }
+                Err(e) => {
+                    holding.error = Some(e);
+                    (holding, None)
+                }
+            }
+        })
+        .collect();
+
+    let total: Decimal = values.iter().filter_map(|(_, value)| *value).sum();
+
+    let holdings = values
+        .into_iter()
+        .map(|(mut holding, value)| {
+            if let (Some(value), false) = (value, total.is_zero()) {
+                let share = value / total * Decimal::ONE_HUNDRED;
+                holding.share_percent = Some(
+                    RoundingMode::HalfEven
+                        .round(share, SHARE_DECIMAL_PLACES)
+                        .to_string(),
+                );
+            }
+
+            holding
+        })
+        .collect();
+
+    PortfolioValuation {
+        reporting_currency: reporting_currency.get_code().clone(),
+        total: reporting_currency
+            .format_amount(reporting_currency.round_amount_with(total, rounding)),
+        rate_timestamp: rate_table
+            .get_updated_at()
+            .map(|updated_at| updated_at.to_rfc3339()),
+        holdings,
+    }
+}
+
+fn value_holding(
+    row: &BatchRow,
+    currency: &str,
+    rate_table: &RateTable,
+    parser: &AmountParser,
+) -> Result<(Decimal, Decimal), String> {
+    if let Some(error) = &row.error {
+        return Err(error.clone());
+    }
+
+    if !(2..=3).contains(&row.columns.len()) {
+        return Err(format!(
+            "Expected currency, amount and an optional label, found {} columns",
+            row.columns.len()
+        ));
+    }
+
+    let amount = parser
+        .clone()
+        .for_currency(&Currency::new_from_code(currency.to_string()))
+        .parse(row.get_column(1))?;
+    let rate = rate_table.get_cross_rate(currency, rate_table.get_base().get_code())?;
+
+    let value = amount
+        .checked_mul(rate)
+        .ok_or_else(|| format!("{} {} is too large to value", amount, currency))?;
+
+    Ok((rate, value))
+}
+
+pub async fn display_portfolio_value(
+    input: &str,
+    currency: &str,
+    format: OutputFormat,
+    rounding: RoundingMode,
+) -> Result<CommandHistory, String> {
+    let reader = open_input(Some(input))?;
+    let rows = read_csv_rows(reader, "currency");
+
+    // one rate table of the reporting currency covers every holding
+    let rate_table = get_rate_table(currency).await?;
+
+    let valuation = value_holdings(&rows, &rate_table, rounding);
+
+    match format {
+        OutputFormat::Json => match serde_json::to_string_pretty(&valuation) {
+            Ok(json) => println!("{}", json),
+            Err(e) => return Err(e.to_string()),
+        },
+        _ => print_valuation(&valuation),
+    }
+
+    let failed = valuation.get_failed_holdings();
+    if failed > 0 && format != OutputFormat::Json {
+        print_warning(&format!(
+            "{} of {} holdings could not be valued and are not included in the total",
+            failed,
+            valuation.holdings.len()
+        ));
+    }
+
+    let output = format!(
+        "Total: {} {}",
+        valuation.total, valuation.reporting_currency
+    );
+    Ok(CommandHistory::new(
+        format!("value {} {}", input, valuation.reporting_currency),
+        Some(rounding),
+    )
+    .with_summary(output)
+    .with_rates(&rate_table))
+}
+
+fn print_valuation(valuation: &PortfolioValuation) {
+    let value_header = format!("Value ({})", valuation.reporting_currency);
+    let mut table = Table::new(vec![
+        "Holding",
+        "Currency",
+        "Amount",
+        "Rate",
+        value_header.as_str(),
+        "Share",
+    ]);
+
+    for holding in &valuation.holdings {
+        let label = holding
+            .label
+            .clone()
+            .unwrap_or_else(|| format!("line {}", holding.line));
+
+        let row = match &holding.error {
+            Some(e) => vec![label, holding.currency.clone(), e.clone()],
+            None => vec![
+                label,
+                holding.currency.clone(),
+                holding.amount.clone(),
+                holding.rate.clone().unwrap_or_default(),
+                holding.value.clone().unwrap_or_default(),
+                holding
+                    .share_percent
+                    .as_ref()
+                    .map(|share| format!("{}%", share))
+                    .unwrap_or_default(),
+            ],
+        };
+        table.add_row(row);
+    }
+
+    match &valuation.rate_timestamp {
+        Some(rate_timestamp) => println!(
+            "Portfolio value in {}  // rates as of {}",
+            valuation.reporting_currency.bold(),
+            rate_timestamp
+        ),
+        None => println!("Portfolio value in {}", valuation.reporting_currency.bold()),
+    }
+    table.print();
+    println!(
+        "{} {} {}",
+        "Total:".bold(),
+        valuation.total.green(),
+        valuation.reporting_currency
+    );
+}
@@ -0,0 +1,46 @@
+use crate::currency::search::search_currencies;
+
+use super::{options::CliFlag, table::Table};
+
+pub const DEFAULT_SEARCH_LIMIT: usize = 10;
+
+pub fn get_search_limit(flags: &[CliFlag]) -> Result<usize, String> {
+    let mut limit = DEFAULT_SEARCH_LIMIT;
+
+    for flag in flags {
+        match flag.name.as_str() {
+            "limit" => match flag.get_value()?.parse::<usize>() {
+                Ok(value) if value > 0 => limit = value,
+                _ => return Err("Option --limit must be a positive whole number".to_string()),
+            },
+            _ => return Err(flag.unknown()),
+        }
+    }
+
+    Ok(limit)
+}
+
+pub async fn display_search(query: &str, limit: usize) -> Result<(), String> {
+    let matches = search_currencies(query).await;
+
+    if matches.is_empty() {
+        return Err(format!("No currency matches {}", query));
+    }
+
+    let mut table = Table::new(vec!["Code", "Name", "Symbol", "Matched by"]);
+
+    for currency_match in matches.into_iter().take(limit) {
+        let currency = currency_match.currency;
+
+        table.add_row(vec![
+            currency.get_code().clone(),
+            currency.get_name().to_string(),
+            currency.get_symbol().to_string(),
+            currency_match.reason.to_string(),
+        ]);
+    }
+
+    table.print();
+
+    Ok(())
+}
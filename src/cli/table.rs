@@ -0,0 +1,87 @@
+use colored::Colorize;
+
+pub struct Table {
+    headers: Vec<String>,
+    rows: Vec<Vec<String>>,
+}
+
+impl Table {
+    pub fn new(headers: Vec<&str>) -> Table {
+        Table {
+            headers: headers.iter().map(|header| header.to_string()).collect(),
+            rows: vec![],
+        }
+    }
+
+    pub fn add_row(&mut self, row: Vec<String>) {
+        self.rows.push(row);
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rows.is_empty()
+    }
+
+    pub fn get_lines(&self) -> Vec<String> {
+        let widths = self.get_column_widths();
+
+        let mut lines = vec![Table::format_row(&self.headers, &widths)];
+        for row in &self.rows {
+            lines.push(Table::format_row(row, &widths));
+        }
+
+        lines
+    }
+
+    pub fn print(&self) {
+        let lines = self.get_lines();
+
+        for (index, line) in lines.iter().enumerate() {
+            if index == 0 {
+                println!("{}", line.bold());
+            } else {
+                println!("{}", line);
+            }
+        }
+    }
+
+    fn get_column_widths(&self) -> Vec<usize> {
+        let mut widths: Vec<usize> = self
+            .headers
+            .iter()
+            .map(|header| header.chars().count())
+            .collect();
+
+        for row in &self.rows {
+            for (index, cell) in row.iter().enumerate() {
+                let width = cell.chars().count();
+
+                match widths.get_mut(index) {
+                    Some(current) if *current < width => *current = width,
+                    Some(_) => (),
+                    None => widths.push(width),
+                }
+            }
+        }
+
+        widths
+    }
+
+    // first column is left aligned, the remaining (numeric) columns are right aligned
+    fn format_row(row: &[String], widths: &[usize]) -> String {
+        let cells: Vec<String> = row
+            .iter()
+            .enumerate()
+            .map(|(index, cell)| {
+                let width = widths[index];
+
+                if index == 0 {
+                    format!("{:<width$}", cell, width = width)
+                } else {
+                    format!("{:>width$}", cell, width = width)
+                }
+            })
+            .collect();
+
+        cells.join("  ").trim_end().to_string()
+    }
+}
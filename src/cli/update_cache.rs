@@ -3,7 +3,7 @@ use crate::{
     error::print_error,
 };
 
-pub async fn update_cache() {
+pub async fn update_cache() -> Result<(), String> {
     let reset_cache_folders = rest_cache().await;
 
     match reset_cache_folders {
@@ -15,14 +15,8 @@ pub async fn update_cache() {
         }
     }
 
-    let supported_currencies_with_rates = get_supported_currencies_with_rates().await;
+    get_supported_currencies_with_rates().await?;
+    println!("Cache updated");
 
-    match supported_currencies_with_rates {
-        Ok(_) => {
-            println!("Cache updated");
-        }
-        Err(e) => {
-            print_error(e.to_string().as_str());
-        }
-    }
+    Ok(())
 }
@@ -1 +1,287 @@
+use std::{fmt, str::FromStr};
 
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+use crate::currency::Currency;
+
+use super::rounding::RoundingMode;
+
+pub const SPREAD_PERCENT_ENV: &str = "CONVERSION_SPREAD_PERCENT";
+pub const FIXED_FEE_ENV: &str = "CONVERSION_FIXED_FEE";
+pub const FIXED_FEE_CURRENCY_ENV: &str = "CONVERSION_FIXED_FEE_CURRENCY";
+pub const MINIMUM_FEE_ENV: &str = "CONVERSION_MINIMUM_FEE";
+
+const MAX_REVERSE_ADJUSTMENTS: u32 = 100;
+
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "kebab-case")]
+pub enum FeeCurrency {
+    #[default]
+    Source,
+    Target,
+}
+
+impl FromStr for FeeCurrency {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "source" => Ok(FeeCurrency::Source),
+            "target" => Ok(FeeCurrency::Target),
+            _ => Err(format!(
+                "{} is not a valid fee currency, use source or target",
+                s
+            )),
+        }
+    }
+}
+
+impl fmt::Display for FeeCurrency {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FeeCurrency::Source => write!(f, "source"),
+            FeeCurrency::Target => write!(f, "target"),
+        }
+    }
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Default)]
+pub struct FeeSchedule {
+    pub spread_percent: Decimal,
+    pub fixed_fee: Decimal,
+    pub fixed_fee_currency: FeeCurrency,
+    // always expressed in the target currency
+    pub minimum_fee: Decimal,
+}
+
+impl FeeSchedule {
+    pub fn from_env() -> FeeSchedule {
+        dotenv::dotenv().ok();
+
+        FeeSchedule {
+            spread_percent: read_decimal_env(SPREAD_PERCENT_ENV),
+            fixed_fee: read_decimal_env(FIXED_FEE_ENV),
+            fixed_fee_currency: match std::env::var(FIXED_FEE_CURRENCY_ENV) {
+                Ok(val) => val.parse::<FeeCurrency>().unwrap_or_default(),
+                Err(_) => FeeCurrency::default(),
+            },
+            minimum_fee: read_decimal_env(MINIMUM_FEE_ENV),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.spread_percent.is_zero() && self.fixed_fee.is_zero() && self.minimum_fee.is_zero()
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        if self.spread_percent.is_sign_negative() || self.spread_percent >= Decimal::ONE_HUNDRED {
+            return Err(format!(
+                "Spread must be between 0 and 100 percent, got {}",
+                self.spread_percent
+            ));
+        }
+
+        if self.fixed_fee.is_sign_negative() || self.minimum_fee.is_sign_negative() {
+            return Err("Fees cannot be negative".to_string());
+        }
+
+        Ok(())
+    }
+}
+
+fn read_decimal_env(key: &str) -> Decimal {
+    match std::env::var(key) {
+        Ok(val) => Decimal::from_str(val.trim()).unwrap_or_default(),
+        Err(_) => Decimal::ZERO,
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConversionBreakdown {
+    pub amount: Decimal,
+    pub mid_market_rate: Decimal,
+    pub effective_rate: Decimal,
+    pub mid_market_result: Decimal,
+    pub fees: Decimal,
+    pub net_result: Decimal,
+}
+
+#[derive(Debug, Clone)]
+pub struct Converter {
+    source: Currency,
+    target: Currency,
+    mid_market_rate: Decimal,
+    fees: FeeSchedule,
+    rounding: RoundingMode,
+}
+
+impl Converter {
+    pub fn new(source: Currency, target: Currency, mid_market_rate: Decimal) -> Converter {
+        Converter {
+            source,
+            target,
+            mid_market_rate,
+            fees: FeeSchedule::default(),
+            rounding: RoundingMode::default(),
+        }
+    }
+
+    pub fn with_fees(mut self, fees: FeeSchedule) -> Converter {
+        self.fees = fees;
+        self
+    }
+
+    pub fn with_rounding(mut self, rounding: RoundingMode) -> Converter {
+        self.rounding = rounding;
+        self
+    }
+
+    pub fn get_source(&self) -> &Currency {
+        &self.source
+    }
+
+    pub fn get_target(&self) -> &Currency {
+        &self.target
+    }
+
+    pub fn get_fees(&self) -> &FeeSchedule {
+        &self.fees
+    }
+
+    pub fn get_rounding(&self) -> RoundingMode {
+        self.rounding
+    }
+
+    pub fn get_effective_rate(&self) -> Decimal {
+        let spread = self.fees.spread_percent / Decimal::ONE_HUNDRED;
+
+        self.mid_market_rate * (Decimal::ONE - spread)
+    }
+
+    pub fn convert(&self, amount: Decimal) -> Result<ConversionBreakdown, String> {
+        self.fees.validate()?;
+
+        let out_of_range = || out_of_range(amount, &self.source);
+
+        let effective_rate = self.get_effective_rate();
+        let mid_market_result = amount
+            .checked_mul(self.mid_market_rate)
+            .ok_or_else(out_of_range)?;
+        let mid_market_result = self.round(mid_market_result);
+
+        let net_result = match self.fees.fixed_fee_currency {
+            FeeCurrency::Source => amount
+                .checked_sub(self.fees.fixed_fee)
+                .and_then(|amount| amount.checked_mul(effective_rate)),
+            FeeCurrency::Target => amount
+                .checked_mul(effective_rate)
+                .and_then(|result| result.checked_sub(self.fees.fixed_fee)),
+        };
+        let mut net_result = self.round(net_result.ok_or_else(out_of_range)?);
+
+        let fees = mid_market_result
+            .checked_sub(net_result)
+            .ok_or_else(out_of_range)?;
+        if fees < self.fees.minimum_fee {
+            let net_result_after_minimum_fee = mid_market_result
+                .checked_sub(self.fees.minimum_fee)
+                .ok_or_else(out_of_range)?;
+            net_result = self.round(net_result_after_minimum_fee);
+        }
+
+        if net_result.is_sign_negative() && !net_result.is_zero() {
+            return Err(format!(
+                "{} {} does not cover the conversion fees",
+                amount,
+                self.source.get_code()
+            ));
+        }
+
+        Ok(ConversionBreakdown {
+            amount,
+            mid_market_rate: self.mid_market_rate,
+            effective_rate,
+            mid_market_result,
+            fees: mid_market_result - net_result,
+            net_result,
+        })
+    }
+
+    pub fn convert_reverse(&self, net_result: Decimal) -> Result<ConversionBreakdown, String> {
+        self.fees.validate()?;
+
+        let effective_rate = self.get_effective_rate();
+
+        if effective_rate <= Decimal::ZERO {
+            return Err(format!(
+                "Exchange rate for {} is not positive",
+                self.target.get_code()
+            ));
+        }
+
+        let out_of_range = || out_of_range(net_result, &self.target);
+
+        let amount_before_minimum_fee = match self.fees.fixed_fee_currency {
+            FeeCurrency::Source => net_result
+                .checked_div(effective_rate)
+                .and_then(|amount| amount.checked_add(self.fees.fixed_fee)),
+            FeeCurrency::Target => net_result
+                .checked_add(self.fees.fixed_fee)
+                .and_then(|result| result.checked_div(effective_rate)),
+        };
+        let amount_with_minimum_fee = net_result
+            .checked_add(self.fees.minimum_fee)
+            .and_then(|result| result.checked_div(self.mid_market_rate));
+        let amount_before_minimum_fee = amount_before_minimum_fee.ok_or_else(out_of_range)?;
+        let amount_with_minimum_fee = amount_with_minimum_fee.ok_or_else(out_of_range)?;
+
+        let source_minor_units = self.source.get_minor_units();
+        let minor_unit = Decimal::new(1, source_minor_units);
+        let mut amount = RoundingMode::Ceiling.round(
+            amount_before_minimum_fee.max(amount_with_minimum_fee),
+            source_minor_units,
+        );
+
+        let target = self.round(net_result);
+
+        // rounding of the result can move the needed amount a few minor units either way
+        for _ in 0..MAX_REVERSE_ADJUSTMENTS {
+            if self.convert(amount)?.net_result >= target {
+                break;
+            }
+
+            amount = amount.checked_add(minor_unit).ok_or_else(out_of_range)?;
+        }
+
+        for _ in 0..MAX_REVERSE_ADJUSTMENTS {
+            let lower_amount = amount - minor_unit;
+
+            match self.convert(lower_amount) {
+                Ok(breakdown) if breakdown.net_result >= target => amount = lower_amount,
+                _ => break,
+            }
+        }
+
+        let breakdown = self.convert(amount)?;
+
+        if breakdown.net_result < target {
+            return Err(format!(
+                "Could not find the {} amount needed to receive {} {}",
+                self.source.get_code(),
+                net_result,
+                self.target.get_code()
+            ));
+        }
+
+        Ok(breakdown)
+    }
+
+    fn round(&self, amount: Decimal) -> Decimal {
+        self.target.round_amount_with(amount, self.rounding)
+    }
+}
+
+fn out_of_range(amount: Decimal, currency: &Currency) -> String {
+    format!("{} {} is too large to convert", amount, currency.get_code())
+}
@@ -0,0 +1,419 @@
+use std::{collections::HashMap, fmt, str::FromStr};
+
+use rust_decimal::Decimal;
+
+use crate::currency::Currency;
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Number(Decimal),
+    Code(String),
+    Plus,
+    Minus,
+    Star,
+    Slash,
+    LeftParen,
+    RightParen,
+    In,
+}
+
+impl fmt::Display for Token {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Token::Number(number) => write!(f, "{}", number),
+            Token::Code(code) => write!(f, "{}", code),
+            Token::Plus => write!(f, "+"),
+            Token::Minus => write!(f, "-"),
+            Token::Star => write!(f, "*"),
+            Token::Slash => write!(f, "/"),
+            Token::LeftParen => write!(f, "("),
+            Token::RightParen => write!(f, ")"),
+            Token::In => write!(f, "in"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Operator {
+    Add,
+    Subtract,
+    Multiply,
+    Divide,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Expression {
+    Money {
+        amount: Decimal,
+        currency: Option<String>,
+    },
+    Negate(Box<Expression>),
+    Binary {
+        operator: Operator,
+        left: Box<Expression>,
+        right: Box<Expression>,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConversionExpression {
+    expression: Expression,
+    target: Currency,
+}
+
+// a value is either an amount of money already converted into the target currency or a plain number
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Value {
+    amount: Decimal,
+    is_money: bool,
+}
+
+impl ConversionExpression {
+    pub fn parse(input: &str) -> Result<ConversionExpression, String> {
+        let tokens = tokenize(input)?;
+        let mut parser = Parser {
+            tokens,
+            position: 0,
+        };
+
+        let expression = parser.parse_expression()?;
+
+        match parser.next() {
+            Some(Token::In) => (),
+            Some(token) => return Err(format!("Unexpected '{}' in expression", token)),
+            None => {
+                return Err(
+                    "Missing target currency, end the expression with 'in <currency>'".to_string(),
+                )
+            }
+        }
+
+        let target = match parser.next() {
+            Some(Token::Code(code)) => Currency::new_from_code(code),
+            _ => return Err("Expected a currency code after 'in'".to_string()),
+        };
+
+        if let Some(token) = parser.next() {
+            return Err(format!("Unexpected '{}' after the target currency", token));
+        }
+
+        Ok(ConversionExpression { expression, target })
+    }
+
+    pub fn get_target(&self) -> &Currency {
+        &self.target
+    }
+
+    pub fn get_expression(&self) -> &Expression {
+        &self.expression
+    }
+
+    pub fn get_currencies(&self) -> Vec<String> {
+        let mut currencies: Vec<String> = vec![];
+        collect_currencies(&self.expression, &mut currencies);
+
+        currencies
+    }
+
+    // rates map each currency used in the expression to its rate into the target currency
+    pub fn evaluate(&self, rates: &HashMap<String, Decimal>) -> Result<Decimal, String> {
+        let value = evaluate(&self.expression, self.target.get_code(), rates)?;
+
+        if !value.is_money {
+            return Err("Expression does not contain any amount of money".to_string());
+        }
+
+        Ok(value.amount)
+    }
+}
+
+pub fn is_conversion_expression(input: &str) -> bool {
+    match tokenize(input) {
+        Ok(tokens) => tokens.contains(&Token::In),
+        Err(_) => false,
+    }
+}
+
+fn collect_currencies(expression: &Expression, currencies: &mut Vec<String>) {
+    match expression {
+        Expression::Money {
+            currency: Some(currency),
+            ..
+        } => {
+            if !currencies.contains(currency) {
+                currencies.push(currency.clone());
+            }
+        }
+        Expression::Money { currency: None, .. } => (),
+        Expression::Negate(expression) => collect_currencies(expression, currencies),
+        Expression::Binary { left, right, .. } => {
+            collect_currencies(left, currencies);
+            collect_currencies(right, currencies);
+        }
+    }
+}
+
+fn evaluate(
+    expression: &Expression,
+    target: &str,
+    rates: &HashMap<String, Decimal>,
+) -> Result<Value, String> {
+    match expression {
+        Expression::Money {
+            amount,
+            currency: None,
+        } => Ok(Value {
+            amount: *amount,
+            is_money: false,
+        }),
+        Expression::Money {
+            amount,
+            currency: Some(currency),
+        } => {
+            let rate = match currency == target {
+                true => Decimal::ONE,
+                false => match rates.get(currency) {
+                    Some(rate) => *rate,
+                    None => return Err(format!("Exchange rate for {} not found", currency)),
+                },
+            };
+
+            Ok(Value {
+                amount: checked(amount.checked_mul(rate))?,
+                is_money: true,
+            })
+        }
+        Expression::Negate(expression) => {
+            let value = evaluate(expression, target, rates)?;
+
+            Ok(Value {
+                amount: -value.amount,
+                is_money: value.is_money,
+            })
+        }
+        Expression::Binary {
+            operator,
+            left,
+            right,
+        } => {
+            let left = evaluate(left, target, rates)?;
+            let right = evaluate(right, target, rates)?;
+
+            apply_operator(*operator, left, right)
+        }
+    }
+}
+
+fn apply_operator(operator: Operator, left: Value, right: Value) -> Result<Value, String> {
+    match operator {
+        Operator::Add | Operator::Subtract => {
+            if left.is_money != right.is_money {
+                return Err(
+                    "Cannot add or subtract a plain number and an amount of money".to_string(),
+                );
+            }
+
+            let amount = match operator {
+                Operator::Add => left.amount.checked_add(right.amount),
+                _ => left.amount.checked_sub(right.amount),
+            };
+
+            Ok(Value {
+                amount: checked(amount)?,
+                is_money: left.is_money,
+            })
+        }
+        Operator::Multiply => {
+            if left.is_money && right.is_money {
+                return Err("Cannot multiply two amounts of money".to_string());
+            }
+
+            Ok(Value {
+                amount: checked(left.amount.checked_mul(right.amount))?,
+                is_money: left.is_money || right.is_money,
+            })
+        }
+        Operator::Divide => {
+            if right.amount.is_zero() {
+                return Err("Division by zero".to_string());
+            }
+
+            if !left.is_money && right.is_money {
+                return Err("Cannot divide a plain number by an amount of money".to_string());
+            }
+
+            Ok(Value {
+                amount: checked(left.amount.checked_div(right.amount))?,
+                // money divided by money is a plain ratio
+                is_money: left.is_money && !right.is_money,
+            })
+        }
+    }
+}
+
+fn checked(amount: Option<Decimal>) -> Result<Decimal, String> {
+    match amount {
+        Some(amount) => Ok(amount),
+        None => Err("Expression result is out of range".to_string()),
+    }
+}
+
+fn tokenize(input: &str) -> Result<Vec<Token>, String> {
+    let mut tokens: Vec<Token> = vec![];
+    let mut chars = input.chars().peekable();
+
+    while let Some(&char) = chars.peek() {
+        if char.is_whitespace() {
+            chars.next();
+            continue;
+        }
+
+        if char.is_ascii_digit() || char == '.' {
+            let mut number = String::new();
+            while let Some(&char) = chars.peek() {
+                if char.is_ascii_digit() || char == '.' {
+                    number.push(char);
+                    chars.next();
+                } else {
+                    break;
+                }
+            }
+
+            let number = Decimal::from_str(&number)
+                .map_err(|_| format!("{} is not a valid number", number))?;
+            tokens.push(Token::Number(number));
+            continue;
+        }
+
+        if char.is_alphabetic() {
+            let mut word = String::new();
+            while let Some(&char) = chars.peek() {
+                if char.is_alphabetic() {
+                    word.push(char);
+                    chars.next();
+                } else {
+                    break;
+                }
+            }
+
+            match word.to_lowercase().as_str() {
+                "in" | "to" => tokens.push(Token::In),
+                _ => tokens.push(Token::Code(word.to_uppercase())),
+            }
+            continue;
+        }
+
+        let token = match char {
+            '+' => Token::Plus,
+            '-' => Token::Minus,
+            '*' | '×' => Token::Star,
+            '/' | '÷' => Token::Slash,
+            '(' => Token::LeftParen,
+            ')' => Token::RightParen,
+            _ => return Err(format!("Unexpected character '{}' in expression", char)),
+        };
+
+        tokens.push(token);
+        chars.next();
+    }
+
+    Ok(tokens)
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    position: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.position)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.position).cloned();
+        self.position += 1;
+
+        token
+    }
+
+    fn parse_expression(&mut self) -> Result<Expression, String> {
+        let mut expression = self.parse_term()?;
+
+        while let Some(operator) = match self.peek() {
+            Some(Token::Plus) => Some(Operator::Add),
+            Some(Token::Minus) => Some(Operator::Subtract),
+            _ => None,
+        } {
+            self.next();
+            let right = self.parse_term()?;
+
+            expression = Expression::Binary {
+                operator,
+                left: Box::new(expression),
+                right: Box::new(right),
+            };
+        }
+
+        Ok(expression)
+    }
+
+    fn parse_term(&mut self) -> Result<Expression, String> {
+        let mut expression = self.parse_unary()?;
+
+        while let Some(operator) = match self.peek() {
+            Some(Token::Star) => Some(Operator::Multiply),
+            Some(Token::Slash) => Some(Operator::Divide),
+            _ => None,
+        } {
+            self.next();
+            let right = self.parse_unary()?;
+
+            expression = Expression::Binary {
+                operator,
+                left: Box::new(expression),
+                right: Box::new(right),
+            };
+        }
+
+        Ok(expression)
+    }
+
+    fn parse_unary(&mut self) -> Result<Expression, String> {
+        if let Some(Token::Minus) = self.peek() {
+            self.next();
+            let expression = self.parse_unary()?;
+
+            return Ok(Expression::Negate(Box::new(expression)));
+        }
+
+        self.parse_primary()
+    }
+
+    fn parse_primary(&mut self) -> Result<Expression, String> {
+        match self.next() {
+            Some(Token::Number(amount)) => {
+                let currency = match self.peek() {
+                    Some(Token::Code(code)) => Some(code.clone()),
+                    _ => None,
+                };
+
+                if currency.is_some() {
+                    self.next();
+                }
+
+                Ok(Expression::Money { amount, currency })
+            }
+            Some(Token::LeftParen) => {
+                let expression = self.parse_expression()?;
+
+                match self.next() {
+                    Some(Token::RightParen) => Ok(expression),
+                    _ => Err("Missing closing parenthesis".to_string()),
+                }
+            }
+            Some(token) => Err(format!("Unexpected '{}' in expression", token)),
+            None => Err("Unexpected end of expression".to_string()),
+        }
+    }
+}
@@ -1,5 +1,32 @@
+use rust_decimal::{prelude::FromPrimitive, prelude::ToPrimitive, Decimal};
+
+use crate::currency::Currency;
+
+use self::rounding::RoundingMode;
+
 pub mod converter;
+pub mod expression;
+pub mod rounding;
 
-pub fn convert(amount: f64, rate: f64) -> f64 {
+pub fn convert_decimal(amount: Decimal, rate: Decimal) -> Decimal {
     amount * rate
 }
+
+pub fn convert_and_round(
+    amount: Decimal,
+    rate: Decimal,
+    target: &Currency,
+    rounding: RoundingMode,
+) -> Decimal {
+    target.round_amount_with(convert_decimal(amount, rate), rounding)
+}
+
+pub fn convert(amount: f64, rate: f64) -> f64 {
+    let amount = Decimal::from_f64(amount);
+    let rate = Decimal::from_f64(rate);
+
+    match (amount, rate) {
+        (Some(amount), Some(rate)) => convert_decimal(amount, rate).to_f64().unwrap_or(f64::NAN),
+        _ => f64::NAN,
+    }
+}
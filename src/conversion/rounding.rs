@@ -0,0 +1,92 @@
+use std::{fmt, str::FromStr};
+
+use rust_decimal::{Decimal, RoundingStrategy};
+use serde::{Deserialize, Serialize};
+
+pub const ROUNDING_MODE_ENV: &str = "ROUNDING_MODE";
+
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "kebab-case")]
+pub enum RoundingMode {
+    HalfUp,
+    #[default]
+    HalfEven,
+    Floor,
+    Ceiling,
+    Truncate,
+}
+
+impl RoundingMode {
+    pub fn from_env() -> RoundingMode {
+        dotenv::dotenv().ok();
+
+        match std::env::var(ROUNDING_MODE_ENV) {
+            Ok(val) => val.parse::<RoundingMode>().unwrap_or_default(),
+            Err(_) => RoundingMode::default(),
+        }
+    }
+
+    pub fn get_all() -> Vec<RoundingMode> {
+        vec![
+            RoundingMode::HalfUp,
+            RoundingMode::HalfEven,
+            RoundingMode::Floor,
+            RoundingMode::Ceiling,
+            RoundingMode::Truncate,
+        ]
+    }
+
+    pub fn round(&self, amount: Decimal, decimal_places: u32) -> Decimal {
+        let mut rounded = amount.round_dp_with_strategy(decimal_places, self.get_strategy());
+        rounded.rescale(decimal_places);
+
+        rounded
+    }
+
+    fn get_strategy(&self) -> RoundingStrategy {
+        match self {
+            RoundingMode::HalfUp => RoundingStrategy::MidpointAwayFromZero,
+            RoundingMode::HalfEven => RoundingStrategy::MidpointNearestEven,
+            RoundingMode::Floor => RoundingStrategy::ToNegativeInfinity,
+            RoundingMode::Ceiling => RoundingStrategy::ToPositiveInfinity,
+            RoundingMode::Truncate => RoundingStrategy::ToZero,
+        }
+    }
+}
+
+impl FromStr for RoundingMode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().replace('_', "-").as_str() {
+            "half-up" => Ok(RoundingMode::HalfUp),
+            "half-even" | "bankers" => Ok(RoundingMode::HalfEven),
+            "floor" => Ok(RoundingMode::Floor),
+            "ceiling" | "ceil" => Ok(RoundingMode::Ceiling),
+            "truncate" => Ok(RoundingMode::Truncate),
+            _ => Err(format!(
+                "{} is not a valid rounding mode, use one of: {}",
+                s,
+                RoundingMode::get_all()
+                    .iter()
+                    .map(|mode| mode.to_string())
+                    .collect::<Vec<String>>()
+                    .join(", ")
+            )),
+        }
+    }
+}
+
+impl fmt::Display for RoundingMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            RoundingMode::HalfUp => "half-up",
+            RoundingMode::HalfEven => "half-even",
+            RoundingMode::Floor => "floor",
+            RoundingMode::Ceiling => "ceiling",
+            RoundingMode::Truncate => "truncate",
+        };
+
+        write!(f, "{}", name)
+    }
+}
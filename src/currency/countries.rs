@@ -0,0 +1,222 @@
+// English short names of countries and territories with the currency they use
+pub const COUNTRY_CURRENCIES: &[(&str, &str)] = &[
+    ("Afghanistan", "AFN"),
+    ("Albania", "ALL"),
+    ("Algeria", "DZD"),
+    ("American Samoa", "USD"),
+    ("Andorra", "EUR"),
+    ("Angola", "AOA"),
+    ("Anguilla", "XCD"),
+    ("Antigua and Barbuda", "XCD"),
+    ("Argentina", "ARS"),
+    ("Armenia", "AMD"),
+    ("Aruba", "AWG"),
+    ("Australia", "AUD"),
+    ("Austria", "EUR"),
+    ("Azerbaijan", "AZN"),
+    ("Bahamas", "BSD"),
+    ("Bahrain", "BHD"),
+    ("Bangladesh", "BDT"),
+    ("Barbados", "BBD"),
+    ("Belarus", "BYN"),
+    ("Belgium", "EUR"),
+    ("Belize", "BZD"),
+    ("Benin", "XOF"),
+    ("Bermuda", "BMD"),
+    ("Bhutan", "BTN"),
+    ("Bolivia", "BOB"),
+    ("Bosnia and Herzegovina", "BAM"),
+    ("Botswana", "BWP"),
+    ("Brazil", "BRL"),
+    ("Brunei", "BND"),
+    ("Bulgaria", "BGN"),
+    ("Burkina Faso", "XOF"),
+    ("Burundi", "BIF"),
+    ("Cambodia", "KHR"),
+    ("Cameroon", "XAF"),
+    ("Canada", "CAD"),
+    ("Cape Verde", "CVE"),
+    ("Cayman Islands", "KYD"),
+    ("Central African Republic", "XAF"),
+    ("Chad", "XAF"),
+    ("Chile", "CLP"),
+    ("China", "CNY"),
+    ("Colombia", "COP"),
+    ("Comoros", "KMF"),
+    ("Congo", "XAF"),
+    ("Costa Rica", "CRC"),
+    ("Croatia", "EUR"),
+    ("Cuba", "CUP"),
+    ("Curaçao", "ANG"),
+    ("Cyprus", "EUR"),
+    ("Czech Republic", "CZK"),
+    ("Czechia", "CZK"),
+    ("Democratic Republic of the Congo", "CDF"),
+    ("Denmark", "DKK"),
+    ("Djibouti", "DJF"),
+    ("Dominica", "XCD"),
+    ("Dominican Republic", "DOP"),
+    ("Ecuador", "USD"),
+    ("Egypt", "EGP"),
+    ("El Salvador", "USD"),
+    ("Equatorial Guinea", "XAF"),
+    ("Eritrea", "ERN"),
+    ("Estonia", "EUR"),
+    ("Eswatini", "SZL"),
+    ("Ethiopia", "ETB"),
+    ("Falkland Islands", "FKP"),
+    ("Faroe Islands", "FOK"),
+    ("Fiji", "FJD"),
+    ("Finland", "EUR"),
+    ("France", "EUR"),
+    ("French Polynesia", "XPF"),
+    ("Gabon", "XAF"),
+    ("Gambia", "GMD"),
+    ("Georgia", "GEL"),
+    ("Germany", "EUR"),
+    ("Ghana", "GHS"),
+    ("Gibraltar", "GIP"),
+    ("Greece", "EUR"),
+    ("Grenada", "XCD"),
+    ("Guam", "USD"),
+    ("Guatemala", "GTQ"),
+    ("Guernsey", "GGP"),
+    ("Guinea", "GNF"),
+    ("Guinea-Bissau", "XOF"),
+    ("Guyana", "GYD"),
+    ("Haiti", "HTG"),
+    ("Honduras", "HNL"),
+    ("Hong Kong", "HKD"),
+    ("Hungary", "HUF"),
+    ("Iceland", "ISK"),
+    ("India", "INR"),
+    ("Indonesia", "IDR"),
+    ("Iran", "IRR"),
+    ("Iraq", "IQD"),
+    ("Ireland", "EUR"),
+    ("Isle of Man", "IMP"),
+    ("Israel", "ILS"),
+    ("Italy", "EUR"),
+    ("Ivory Coast", "XOF"),
+    ("Jamaica", "JMD"),
+    ("Japan", "JPY"),
+    ("Jersey", "JEP"),
+    ("Jordan", "JOD"),
+    ("Kazakhstan", "KZT"),
+    ("Kenya", "KES"),
+    ("Kiribati", "KID"),
+    ("Kosovo", "EUR"),
+    ("Kuwait", "KWD"),
+    ("Kyrgyzstan", "KGS"),
+    ("Laos", "LAK"),
+    ("Latvia", "EUR"),
+    ("Lebanon", "LBP"),
+    ("Lesotho", "LSL"),
+    ("Liberia", "LRD"),
+    ("Libya", "LYD"),
+    ("Liechtenstein", "CHF"),
+    ("Lithuania", "EUR"),
+    ("Luxembourg", "EUR"),
+    ("Macau", "MOP"),
+    ("Madagascar", "MGA"),
+    ("Malawi", "MWK"),
+    ("Malaysia", "MYR"),
+    ("Maldives", "MVR"),
+    ("Mali", "XOF"),
+    ("Malta", "EUR"),
+    ("Marshall Islands", "USD"),
+    ("Mauritania", "MRU"),
+    ("Mauritius", "MUR"),
+    ("Mexico", "MXN"),
+    ("Micronesia", "USD"),
+    ("Moldova", "MDL"),
+    ("Monaco", "EUR"),
+    ("Mongolia", "MNT"),
+    ("Montenegro", "EUR"),
+    ("Montserrat", "XCD"),
+    ("Morocco", "MAD"),
+    ("Mozambique", "MZN"),
+    ("Myanmar", "MMK"),
+    ("Namibia", "NAD"),
+    ("Nauru", "AUD"),
+    ("Nepal", "NPR"),
+    ("Netherlands", "EUR"),
+    ("New Caledonia", "XPF"),
+    ("New Zealand", "NZD"),
+    ("Nicaragua", "NIO"),
+    ("Niger", "XOF"),
+    ("Nigeria", "NGN"),
+    ("North Macedonia", "MKD"),
+    ("Norway", "NOK"),
+    ("Oman", "OMR"),
+    ("Pakistan", "PKR"),
+    ("Palau", "USD"),
+    ("Panama", "PAB"),
+    ("Papua New Guinea", "PGK"),
+    ("Paraguay", "PYG"),
+    ("Peru", "PEN"),
+    ("Philippines", "PHP"),
+    ("Poland", "PLN"),
+    ("Portugal", "EUR"),
+    ("Puerto Rico", "USD"),
+    ("Qatar", "QAR"),
+    ("Romania", "RON"),
+    ("Russia", "RUB"),
+    ("Rwanda", "RWF"),
+    ("Saint Helena", "SHP"),
+    ("Saint Kitts and Nevis", "XCD"),
+    ("Saint Lucia", "XCD"),
+    ("Saint Vincent and the Grenadines", "XCD"),
+    ("Samoa", "WST"),
+    ("San Marino", "EUR"),
+    ("Sao Tome and Principe", "STN"),
+    ("Saudi Arabia", "SAR"),
+    ("Senegal", "XOF"),
+    ("Serbia", "RSD"),
+    ("Seychelles", "SCR"),
+    ("Sierra Leone", "SLE"),
+    ("Singapore", "SGD"),
+    ("Sint Maarten", "ANG"),
+    ("Slovakia", "EUR"),
+    ("Slovenia", "EUR"),
+    ("Solomon Islands", "SBD"),
+    ("Somalia", "SOS"),
+    ("South Africa", "ZAR"),
+    ("South Korea", "KRW"),
+    ("South Sudan", "SSP"),
+    ("Spain", "EUR"),
+    ("Sri Lanka", "LKR"),
+    ("Sudan", "SDG"),
+    ("Suriname", "SRD"),
+    ("Sweden", "SEK"),
+    ("Switzerland", "CHF"),
+    ("Syria", "SYP"),
+    ("Taiwan", "TWD"),
+    ("Tajikistan", "TJS"),
+    ("Tanzania", "TZS"),
+    ("Thailand", "THB"),
+    ("Timor-Leste", "USD"),
+    ("Togo", "XOF"),
+    ("Tonga", "TOP"),
+    ("Trinidad and Tobago", "TTD"),
+    ("Tunisia", "TND"),
+    ("Turkey", "TRY"),
+    ("Turkmenistan", "TMT"),
+    ("Turks and Caicos Islands", "USD"),
+    ("Tuvalu", "TVD"),
+    ("Uganda", "UGX"),
+    ("Ukraine", "UAH"),
+    ("United Arab Emirates", "AED"),
+    ("United Kingdom", "GBP"),
+    ("United States", "USD"),
+    ("Uruguay", "UYU"),
+    ("Uzbekistan", "UZS"),
+    ("Vanuatu", "VUV"),
+    ("Vatican City", "EUR"),
+    ("Venezuela", "VES"),
+    ("Vietnam", "VND"),
+    ("Wallis and Futuna", "XPF"),
+    ("Yemen", "YER"),
+    ("Zambia", "ZMW"),
+    ("Zimbabwe", "ZWG"),
+];
@@ -1,72 +1,905 @@
-use std::io::Error;
+use std::str::FromStr;
 
+use chrono::{DateTime, Duration, NaiveDate, SecondsFormat, Utc};
+use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
 
-use crate::cache::{
-    file_cache::{create_cache_file, read_and_invalid_cache_file},
-    CacheConfigs,
+use crate::{
+    api::PROVIDER,
+    cache::{
+        file_cache::{
+            append_log_entry, log_exists, read_last_log_id, read_legacy_cache_file,
+            read_log_entries, remove_legacy_cache_file, write_last_log_id, write_log_entries,
+        },
+        CacheConfigs,
+    },
+    conversion::{
+        converter::{Converter, FeeSchedule},
+        rounding::RoundingMode,
+    },
 };
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+use super::{Currency, RateTable};
+
+const COMMAND_HISTORY_MAX_ENTRIES_ENV: &str = "COMMAND_HISTORY_MAX_ENTRIES";
+const DEFAULT_MAX_ENTRIES: usize = 1000;
+// the log is pruned on every tenth of the maximum entries saved, it never grows past 110% of it
+const PRUNE_INTERVAL_DIVISOR: usize = 10;
+const COMMAND_HISTORY_RECORDED_COMMANDS_ENV: &str = "COMMAND_HISTORY_RECORDED_COMMANDS";
+// reading the history or the help is not worth recording
+const DEFAULT_UNRECORDED_COMMANDS: [&str; 4] = ["help", "history", "setup", "interactive-mode"];
+
+// applied to every entry on its own, the oldest entries go first
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct HistoryRetention {
+    pub max_age_in_hours: i64,
+    pub max_entries: usize,
+}
+
+// the command types recorded in the history, by the names of Action::get_name
+#[derive(Debug, Clone, PartialEq)]
+pub enum HistoryRecording {
+    All,
+    Except(Vec<String>),
+    Only(Vec<String>),
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Default)]
+#[serde(rename_all = "kebab-case")]
+pub enum HistoryOutcome {
+    #[default]
+    Success,
+    // the arguments could not be parsed
+    InvalidArguments,
+    // the arguments were rejected before running, e.g. an unsupported currency
+    ValidationError,
+    // the command failed while running, e.g. the exchange rates could not be fetched
+    Failed,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ConversionRecord {
+    pub source: String,
+    pub target: String,
+    // the amount of the source currency, also when the amount to receive was given
+    pub amount: Decimal,
+    pub rate: Decimal,
+    pub result: Decimal,
+    #[serde(default)]
+    pub reverse: bool,
+    // replays apply the same fees, the result is after fees
+    #[serde(default)]
+    pub fees: FeeSchedule,
+}
+
+pub const HISTORY_EXPORT_HEADERS: [&str; 13] = [
+    "id",
+    "timestamp",
+    "command",
+    "source",
+    "target",
+    "amount",
+    "rate",
+    "result",
+    "reverse",
+    "rounding",
+    "rate_timestamp",
+    "provider",
+    "summary",
+];
+
+// one row per conversion, entries without conversions get a single row with their summary
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct HistoryExportRow {
+    pub id: usize,
+    pub timestamp: String,
+    pub command: String,
+    pub source: Option<String>,
+    pub target: Option<String>,
+    pub amount: Option<String>,
+    pub rate: Option<String>,
+    pub result: Option<String>,
+    pub reverse: Option<bool>,
+    pub rounding: Option<String>,
+    pub rate_timestamp: Option<String>,
+    pub provider: Option<String>,
+    pub summary: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReplayedConversion {
+    pub conversion: ConversionRecord,
+    pub current_rate: Decimal,
+    // results in the target currency, or the source amounts needed for reverse conversions
+    pub original: Decimal,
+    pub current: Decimal,
+    pub difference: Decimal,
+    pub change_percent: Option<Decimal>,
+}
+
+// conversions match when they satisfy every currency and amount criterion
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct HistoryFilter {
+    pub source: Option<String>,
+    pub target: Option<String>,
+    pub since: Option<NaiveDate>,
+    pub until: Option<NaiveDate>,
+    pub min_amount: Option<Decimal>,
+    pub max_amount: Option<Decimal>,
+    pub text: Option<String>,
+    pub last: Option<usize>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 pub struct CommandHistory {
+    // ids are never reused, 0 marks entries written before ids were stored
+    #[serde(default)]
+    id: usize,
+    #[serde(default)]
+    pinned: bool,
+    command: String,
+    timestamp: String,
+    #[serde(default)]
+    rounding: Option<RoundingMode>,
+    conversions: Vec<ConversionRecord>,
+    #[serde(default)]
+    rate_timestamp: Option<String>,
+    #[serde(default)]
+    provider: Option<String>,
+    // plain text result of commands that are not single conversions
+    #[serde(default)]
+    summary: Option<String>,
+    // the command type and the arguments as they were given
+    #[serde(default)]
+    action: Option<String>,
+    #[serde(default)]
+    arguments: Vec<String>,
+    #[serde(default)]
+    outcome: HistoryOutcome,
+    #[serde(default)]
+    error: Option<String>,
+    #[serde(default)]
+    duration_in_ms: Option<u64>,
+}
+
+// entries written before the history was structured
+#[derive(Debug, Deserialize, Clone)]
+pub struct LegacyCommandHistory {
     command: String,
     output: String,
     timestamp: String,
+    #[serde(default)]
+    rounding: Option<RoundingMode>,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+#[serde(untagged)]
+pub enum StoredCommandHistory {
+    Structured(CommandHistory),
+    Legacy(LegacyCommandHistory),
+}
+
+impl From<StoredCommandHistory> for CommandHistory {
+    fn from(stored: StoredCommandHistory) -> CommandHistory {
+        match stored {
+            StoredCommandHistory::Structured(history) => history,
+            StoredCommandHistory::Legacy(legacy) => CommandHistory::from_legacy(legacy),
+        }
+    }
 }
 
 impl CommandHistory {
-    pub fn new(command: String, output: String) -> CommandHistory {
-        let timestamp = chrono::Utc::now().to_rfc2822();
+    pub fn new(command: String, rounding: Option<RoundingMode>) -> CommandHistory {
+        let timestamp = Utc::now().to_rfc3339_opts(SecondsFormat::Secs, false);
 
         CommandHistory {
+            id: 0,
+            pinned: false,
             command,
-            output,
             timestamp,
+            rounding,
+            conversions: vec![],
+            rate_timestamp: None,
+            provider: None,
+            summary: None,
+            action: None,
+            arguments: vec![],
+            outcome: HistoryOutcome::Success,
+            error: None,
+            duration_in_ms: None,
         }
     }
 
+    pub fn with_conversions(mut self, conversions: Vec<ConversionRecord>) -> CommandHistory {
+        self.conversions = conversions;
+        self
+    }
+
+    pub fn with_rates(mut self, rate_table: &RateTable) -> CommandHistory {
+        self.rate_timestamp = rate_table
+            .get_updated_at()
+            .map(|updated_at| updated_at.to_rfc3339());
+        self.provider = Some(PROVIDER.to_string());
+        self
+    }
+
+    pub fn with_summary(mut self, summary: String) -> CommandHistory {
+        self.summary = Some(summary);
+        self
+    }
+
+    pub fn with_action(mut self, action: &str, arguments: &[String]) -> CommandHistory {
+        self.action = Some(action.to_string());
+        self.arguments = arguments.to_vec();
+        self
+    }
+
+    pub fn with_outcome(
+        mut self,
+        outcome: HistoryOutcome,
+        error: Option<String>,
+    ) -> CommandHistory {
+        self.outcome = outcome;
+        self.error = error;
+        self
+    }
+
+    pub fn with_duration(mut self, duration: std::time::Duration) -> CommandHistory {
+        self.duration_in_ms = Some(duration.as_millis() as u64);
+        self
+    }
+
+    fn from_legacy(legacy: LegacyCommandHistory) -> CommandHistory {
+        let output = strip_ansi_codes(&legacy.output);
+
+        let history = CommandHistory {
+            id: 0,
+            pinned: false,
+            command: legacy.command,
+            timestamp: legacy.timestamp,
+            rounding: legacy.rounding,
+            conversions: vec![],
+            rate_timestamp: None,
+            provider: None,
+            summary: None,
+            action: None,
+            arguments: vec![],
+            outcome: HistoryOutcome::Success,
+            error: None,
+            duration_in_ms: None,
+        };
+
+        match parse_legacy_conversion(&output) {
+            Some(conversion) => history.with_conversions(vec![conversion]),
+            None => history.with_summary(output),
+        }
+    }
+
+    // expired entries are dropped from the log when it is read, and regularly when entries are saved
     pub fn get_commands() -> Result<Vec<CommandHistory>, String> {
-        let history: Result<Vec<CommandHistory>, Error> =
-            read_and_invalid_cache_file(CacheConfigs::CommandHistory, None);
+        migrate_legacy_history();
 
-        match history {
-            Ok(history) => Ok(history),
-            Err(e) => Err(e.to_string()),
+        let history: Vec<CommandHistory> =
+            read_log_entries::<StoredCommandHistory>(CacheConfigs::CommandHistory)
+                .map_err(|e| e.to_string())?
+                .into_iter()
+                .map(CommandHistory::from)
+                .collect();
+
+        let stored = history.len();
+        let last_id = read_last_log_id(CacheConfigs::CommandHistory).unwrap_or(0);
+        let (history, assigned_id) = assign_ids(history, last_id);
+        let history = HistoryRetention::from_env().apply(history, Utc::now());
+
+        if assigned_id > last_id {
+            write_last_log_id(assigned_id, CacheConfigs::CommandHistory)
+                .map_err(|e| e.to_string())?;
         }
+
+        if history.len() < stored || assigned_id > last_id {
+            write_log_entries(&history, CacheConfigs::CommandHistory).map_err(|e| e.to_string())?;
+        }
+
+        Ok(history)
     }
-    pub fn save(output: String, command: String) -> Result<(), String> {
-        let command = CommandHistory::new(command, output);
-        let history = CommandHistory::get_commands();
 
-        let history: Vec<CommandHistory> = match history {
-            Ok(mut history) => {
-                history.push(command);
-                history
-            }
-            Err(_) => {
-                vec![command]
-            }
+    pub fn save(mut self) -> Result<(), String> {
+        migrate_legacy_history();
+
+        // the ids of a log without a counter are assigned first
+        let last_id = match read_last_log_id(CacheConfigs::CommandHistory) {
+            Some(last_id) => last_id,
+            None => CommandHistory::get_commands()
+                .ok()
+                .and_then(|history| history.iter().map(|entry| entry.id).max())
+                .unwrap_or(0),
         };
 
-        let result = create_cache_file(&history, CacheConfigs::CommandHistory, None);
+        // the counter only moves on once the entry is stored
+        self.id = last_id + 1;
+        append_log_entry(&self, CacheConfigs::CommandHistory).map_err(|e| e.to_string())?;
+        write_last_log_id(self.id, CacheConfigs::CommandHistory).map_err(|e| e.to_string())?;
+
+        if HistoryRetention::from_env().is_prune_due(self.id) {
+            CommandHistory::get_commands()?;
+        }
+
+        Ok(())
+    }
+
+    pub fn delete(id: usize) -> Result<(), String> {
+        let history = CommandHistory::get_commands()?;
+        let stored = history.len();
 
-        match result {
-            Ok(_) => Ok(()),
-            Err(e) => Err(e.to_string()),
+        let history: Vec<CommandHistory> =
+            history.into_iter().filter(|entry| entry.id != id).collect();
+
+        if history.len() == stored {
+            return Err(format!("History entry {} not found", id));
         }
+
+        write_log_entries(&history, CacheConfigs::CommandHistory).map_err(|e| e.to_string())
+    }
+
+    // pinned entries are kept, they can only be removed one by one
+    pub fn clear(before: Option<NaiveDate>) -> Result<usize, String> {
+        let history = CommandHistory::get_commands()?;
+        let stored = history.len();
+
+        let history: Vec<CommandHistory> = history
+            .into_iter()
+            .filter(|entry| entry.pinned || !entry.is_before(before))
+            .collect();
+
+        write_log_entries(&history, CacheConfigs::CommandHistory).map_err(|e| e.to_string())?;
+
+        Ok(stored - history.len())
+    }
+
+    pub fn set_pinned(id: usize, pinned: bool) -> Result<(), String> {
+        let mut history = CommandHistory::get_commands()?;
+
+        match history.iter_mut().find(|entry| entry.id == id) {
+            Some(entry) => entry.pinned = pinned,
+            None => return Err(format!("History entry {} not found", id)),
+        }
+
+        write_log_entries(&history, CacheConfigs::CommandHistory).map_err(|e| e.to_string())
+    }
+
+    fn is_before(&self, before: Option<NaiveDate>) -> bool {
+        match (before, self.get_datetime()) {
+            (Some(before), Some(datetime)) => datetime.date_naive() < before,
+            (Some(_), None) => false,
+            (None, _) => true,
+        }
+    }
+
+    pub fn is_pinned(&self) -> bool {
+        self.pinned
+    }
+
+    pub fn get_id(&self) -> usize {
+        self.id
     }
 
     pub fn get_command(&self) -> &String {
         &self.command
     }
 
-    pub fn get_output(&self) -> &String {
-        &self.output
+    // the text shown for the entry, derived from the stored fields
+    pub fn get_output(&self) -> String {
+        match self.conversions.as_slice() {
+            [] => self
+                .summary
+                .clone()
+                .or_else(|| self.error.clone())
+                .unwrap_or_default(),
+            [conversion] => conversion.to_string(),
+            [first, ..] => {
+                let results: Vec<String> = self
+                    .conversions
+                    .iter()
+                    .map(|conversion| {
+                        let source = Currency::new_from_code(conversion.source.clone());
+                        let target = Currency::new_from_code(conversion.target.clone());
+                        match conversion.reverse {
+                            true => format!(
+                                "{} {} for {} {}",
+                                source.format_amount(conversion.amount),
+                                conversion.source,
+                                target.format_amount(conversion.result),
+                                conversion.target
+                            ),
+                            false => format!(
+                                "{} {}",
+                                target.format_amount(conversion.result),
+                                conversion.target
+                            ),
+                        }
+                    })
+                    .collect();
+
+                match first.reverse {
+                    true => format!("needed to receive: {}", results.join(", ")),
+                    false => format!("{} {} = {}", first.amount, first.source, results.join(", ")),
+                }
+            }
+        }
     }
 
     pub fn get_timestamp(&self) -> &String {
         &self.timestamp
     }
+
+    pub fn get_export_rows(&self) -> Vec<HistoryExportRow> {
+        let row = HistoryExportRow {
+            id: self.id,
+            // entries written before ISO 8601 timestamps are converted
+            timestamp: self
+                .get_datetime()
+                .map(|datetime| datetime.to_rfc3339_opts(SecondsFormat::Secs, false))
+                .unwrap_or_else(|| self.timestamp.clone()),
+            command: self.command.clone(),
+            source: None,
+            target: None,
+            amount: None,
+            rate: None,
+            result: None,
+            reverse: None,
+            rounding: self.rounding.map(|rounding| rounding.to_string()),
+            rate_timestamp: self.rate_timestamp.clone(),
+            provider: self.provider.clone(),
+            summary: self.summary.clone(),
+        };
+
+        if self.conversions.is_empty() {
+            return vec![row];
+        }
+
+        self.conversions
+            .iter()
+            .map(|conversion| HistoryExportRow {
+                source: Some(conversion.source.clone()),
+                target: Some(conversion.target.clone()),
+                amount: Some(conversion.amount.to_string()),
+                rate: Some(conversion.rate.to_string()),
+                result: Some(conversion.result.to_string()),
+                reverse: Some(conversion.reverse),
+                ..row.clone()
+            })
+            .collect()
+    }
+
+    pub fn get_datetime(&self) -> Option<DateTime<Utc>> {
+        DateTime::parse_from_rfc2822(&self.timestamp)
+            .or_else(|_| DateTime::parse_from_rfc3339(&self.timestamp))
+            .ok()
+            .map(|datetime| datetime.with_timezone(&Utc))
+    }
+
+    pub fn get_rounding(&self) -> Option<RoundingMode> {
+        self.rounding
+    }
+
+    pub fn get_conversions(&self) -> &Vec<ConversionRecord> {
+        &self.conversions
+    }
+
+    pub fn get_rate_timestamp(&self) -> Option<&String> {
+        self.rate_timestamp.as_ref()
+    }
+
+    pub fn get_provider(&self) -> Option<&String> {
+        self.provider.as_ref()
+    }
+
+    pub fn get_summary(&self) -> Option<&String> {
+        self.summary.as_ref()
+    }
+
+    pub fn get_action(&self) -> Option<&String> {
+        self.action.as_ref()
+    }
+
+    pub fn get_arguments(&self) -> &Vec<String> {
+        &self.arguments
+    }
+
+    pub fn get_outcome(&self) -> HistoryOutcome {
+        self.outcome
+    }
+
+    pub fn get_error(&self) -> Option<&String> {
+        self.error.as_ref()
+    }
+
+    pub fn get_duration_in_ms(&self) -> Option<u64> {
+        self.duration_in_ms
+    }
+}
+
+impl std::fmt::Display for ConversionRecord {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let source = Currency::new_from_code(self.source.clone());
+        let target = Currency::new_from_code(self.target.clone());
+
+        match self.reverse {
+            true => write!(
+                f,
+                "{} {} needed to receive {} {}  // exchange rate: {}",
+                source.format_amount(self.amount),
+                self.source,
+                target.format_amount(self.result),
+                self.target,
+                self.rate
+            ),
+            false => write!(
+                f,
+                "{} {} = {} {}  // exchange rate: {}",
+                self.amount,
+                self.source,
+                target.format_amount(self.result),
+                self.target,
+                self.rate
+            ),
+        }
+    }
+}
+
+impl ConversionRecord {
+    // the currency the original and the replayed conversion are compared in
+    pub fn get_compared_currency(&self) -> &String {
+        match self.reverse {
+            true => &self.source,
+            false => &self.target,
+        }
+    }
+
+    pub fn replay(
+        &self,
+        rate_table: &RateTable,
+        rounding: RoundingMode,
+    ) -> Result<ReplayedConversion, String> {
+        let current_rate = rate_table.get_cross_rate(&self.source, &self.target)?;
+        let converter = Converter::new(
+            Currency::new_from_code(self.source.clone()),
+            Currency::new_from_code(self.target.clone()),
+            current_rate,
+        )
+        .with_fees(self.fees.clone())
+        .with_rounding(rounding);
+
+        let (original, current) = match self.reverse {
+            true => (self.amount, converter.convert_reverse(self.result)?.amount),
+            false => (self.result, converter.convert(self.amount)?.net_result),
+        };
+        let difference = current - original;
+
+        Ok(ReplayedConversion {
+            conversion: self.clone(),
+            current_rate,
+            original,
+            current,
+            difference,
+            change_percent: match original.is_zero() {
+                true => None,
+                false => Some(difference / original * Decimal::ONE_HUNDRED),
+            },
+        })
+    }
+}
+
+pub fn strip_ansi_codes(text: &str) -> String {
+    let mut stripped = String::with_capacity(text.len());
+    let mut chars = text.chars();
+
+    while let Some(char) = chars.next() {
+        if char == '\u{1b}' {
+            // escape sequences end with a letter, like the m of colour codes
+            for char in chars.by_ref() {
+                if char.is_ascii_alphabetic() {
+                    break;
+                }
+            }
+        } else {
+            stripped.push(char);
+        }
+    }
+
+    stripped
+}
+
+// legacy outputs of single conversions, "100 USD = 92.00 EUR  // exchange rate: 0.92"
+// or "108.70 USD needed to receive 100.00 EUR  // exchange rate: 0.92"
+fn parse_legacy_conversion(output: &str) -> Option<ConversionRecord> {
+    let (conversion, rate) = output.split_once("  // exchange rate: ")?;
+    let rate = Decimal::from_str(rate.trim()).ok()?;
+
+    let (reverse, (source, target)) = match conversion.split_once(" needed to receive ") {
+        Some(parts) => (true, parts),
+        None => (false, conversion.split_once(" = ")?),
+    };
+
+    let parse_side = |side: &str| -> Option<(Decimal, String)> {
+        let (amount, code) = side.trim().split_once(' ')?;
+        let code = code.trim();
+
+        match code.len() == 3 && code.chars().all(|char| char.is_ascii_alphabetic()) {
+            true => Some((Decimal::from_str(amount).ok()?, code.to_uppercase())),
+            false => None,
+        }
+    };
+
+    let (amount, source) = parse_side(source)?;
+    let (result, target) = parse_side(target)?;
+
+    Some(ConversionRecord {
+        source,
+        target,
+        amount,
+        rate,
+        result,
+        reverse,
+        fees: FeeSchedule::default(),
+    })
+}
+
+impl std::fmt::Display for HistoryOutcome {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let outcome = match self {
+            HistoryOutcome::Success => "success",
+            HistoryOutcome::InvalidArguments => "invalid arguments",
+            HistoryOutcome::ValidationError => "validation error",
+            HistoryOutcome::Failed => "failed",
+        };
+
+        write!(f, "{}", outcome)
+    }
+}
+
+impl HistoryRecording {
+    pub fn from_env() -> HistoryRecording {
+        dotenv::dotenv().ok();
+
+        match std::env::var(COMMAND_HISTORY_RECORDED_COMMANDS_ENV) {
+            Ok(val) => HistoryRecording::parse(&val),
+            Err(_) => HistoryRecording::default(),
+        }
+    }
+
+    // a comma separated list of command types, "all", "none", or types prefixed with "-" to skip
+    pub fn parse(value: &str) -> HistoryRecording {
+        let names: Vec<String> = value
+            .split(',')
+            .map(|name| name.trim().to_lowercase())
+            .filter(|name| !name.is_empty())
+            .collect();
+
+        match names.first().map(String::as_str) {
+            None => HistoryRecording::default(),
+            Some("all") if names.len() == 1 => HistoryRecording::All,
+            Some("none") if names.len() == 1 => HistoryRecording::Only(vec![]),
+            _ if names.iter().all(|name| name.starts_with('-')) => HistoryRecording::Except(
+                names
+                    .iter()
+                    .map(|name| name.trim_start_matches('-').to_string())
+                    .collect(),
+            ),
+            _ => HistoryRecording::Only(names),
+        }
+    }
+
+    pub fn records(&self, action: &str) -> bool {
+        match self {
+            HistoryRecording::All => true,
+            HistoryRecording::Except(names) => !names.iter().any(|name| name == action),
+            HistoryRecording::Only(names) => names.iter().any(|name| name == action),
+        }
+    }
+}
+
+impl Default for HistoryRecording {
+    fn default() -> Self {
+        HistoryRecording::Except(
+            DEFAULT_UNRECORDED_COMMANDS
+                .iter()
+                .map(|name| name.to_string())
+                .collect(),
+        )
+    }
+}
+
+impl HistoryRetention {
+    pub fn from_env() -> HistoryRetention {
+        dotenv::dotenv().ok();
+
+        let max_entries = match std::env::var(COMMAND_HISTORY_MAX_ENTRIES_ENV) {
+            Ok(val) => val.parse::<usize>().unwrap_or(DEFAULT_MAX_ENTRIES),
+            Err(_) => DEFAULT_MAX_ENTRIES,
+        };
+
+        HistoryRetention {
+            max_age_in_hours: CacheConfigs::CommandHistory
+                .get_config(None)
+                .get_lifetime_in_hours() as i64,
+            max_entries,
+        }
+    }
+
+    pub fn is_prune_due(&self, saved_id: usize) -> bool {
+        saved_id.is_multiple_of((self.max_entries / PRUNE_INTERVAL_DIVISOR).max(1))
+    }
+
+    pub fn apply(&self, history: Vec<CommandHistory>, now: DateTime<Utc>) -> Vec<CommandHistory> {
+        let oldest = Duration::try_hours(self.max_age_in_hours)
+            .and_then(|max_age| now.checked_sub_signed(max_age));
+
+        let history: Vec<CommandHistory> = history
+            .into_iter()
+            .filter(|entry| match (entry.pinned, oldest, entry.get_datetime()) {
+                (false, Some(oldest), Some(datetime)) => datetime >= oldest,
+                _ => true,
+            })
+            .collect();
+
+        // pinned entries don't count towards the maximum
+        let unpinned = history.iter().filter(|entry| !entry.pinned).count();
+        let mut skipped = unpinned.saturating_sub(self.max_entries);
+
+        history
+            .into_iter()
+            .filter(|entry| {
+                if entry.pinned || skipped == 0 {
+                    return true;
+                }
+
+                skipped -= 1;
+                false
+            })
+            .collect()
+    }
+}
+
+// entries without an id get the next ids in the order they were written
+pub fn assign_ids(history: Vec<CommandHistory>, last_id: usize) -> (Vec<CommandHistory>, usize) {
+    let mut last_id = history
+        .iter()
+        .map(|entry| entry.id)
+        .max()
+        .unwrap_or(0)
+        .max(last_id);
+
+    let history = history
+        .into_iter()
+        .map(|mut entry| {
+            if entry.id == 0 {
+                last_id += 1;
+                entry.id = last_id;
+            }
+            entry
+        })
+        .collect();
+
+    (history, last_id)
+}
+
+// the history used to be a single JSON file that expired as a whole
+fn migrate_legacy_history() {
+    if log_exists(CacheConfigs::CommandHistory) {
+        return;
+    }
+
+    let legacy =
+        match read_legacy_cache_file::<Vec<StoredCommandHistory>>(CacheConfigs::CommandHistory) {
+            Some(legacy) => legacy,
+            None => return,
+        };
+
+    let history: Vec<CommandHistory> = legacy.into_iter().map(CommandHistory::from).collect();
+
+    if write_log_entries(&history, CacheConfigs::CommandHistory).is_ok() {
+        let _ = remove_legacy_cache_file(CacheConfigs::CommandHistory);
+    }
+}
+
+impl HistoryFilter {
+    pub fn with_pair(mut self, pair: &str) -> Result<HistoryFilter, String> {
+        let codes: Vec<String> = match pair.split_once(['/', '-', ':']) {
+            Some((source, target)) => vec![source.to_string(), target.to_string()],
+            None if pair.len() == 6 && pair.is_ascii() => {
+                vec![pair[..3].to_string(), pair[3..].to_string()]
+            }
+            None => vec![],
+        };
+
+        match codes.as_slice() {
+            [source, target] if !source.trim().is_empty() && !target.trim().is_empty() => {
+                self.source = Some(source.trim().to_uppercase());
+                self.target = Some(target.trim().to_uppercase());
+                Ok(self)
+            }
+            _ => Err(format!(
+                "{} is not a valid currency pair, example: USD/EUR",
+                pair
+            )),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        *self == HistoryFilter::default()
+    }
+
+    fn filters_conversions(&self) -> bool {
+        self.source.is_some()
+            || self.target.is_some()
+            || self.min_amount.is_some()
+            || self.max_amount.is_some()
+    }
+
+    fn matches_conversion(&self, conversion: &ConversionRecord) -> bool {
+        self.source
+            .as_ref()
+            .is_none_or(|source| source.eq_ignore_ascii_case(&conversion.source))
+            && self
+                .target
+                .as_ref()
+                .is_none_or(|target| target.eq_ignore_ascii_case(&conversion.target))
+            && self
+                .min_amount
+                .is_none_or(|min_amount| conversion.amount >= min_amount)
+            && self
+                .max_amount
+                .is_none_or(|max_amount| conversion.amount <= max_amount)
+    }
+
+    pub fn matches(&self, entry: &CommandHistory) -> bool {
+        if self.filters_conversions()
+            && !entry
+                .get_conversions()
+                .iter()
+                .any(|conversion| self.matches_conversion(conversion))
+        {
+            return false;
+        }
+
+        if self.since.is_some() || self.until.is_some() {
+            let date = match entry.get_datetime() {
+                Some(datetime) => datetime.date_naive(),
+                None => return false,
+            };
+
+            if self.since.is_some_and(|since| date < since)
+                || self.until.is_some_and(|until| date > until)
+            {
+                return false;
+            }
+        }
+
+        match &self.text {
+            Some(text) => {
+                let text = text.to_lowercase();
+                entry.get_command().to_lowercase().contains(&text)
+                    || entry.get_output().to_lowercase().contains(&text)
+            }
+            None => true,
+        }
+    }
+
+    // entries keep their order, --last keeps the most recent matches
+    pub fn apply(&self, history: Vec<CommandHistory>) -> Vec<CommandHistory> {
+        let mut history: Vec<CommandHistory> = history
+            .into_iter()
+            .filter(|entry| self.matches(entry))
+            .collect();
+
+        if let Some(last) = self.last {
+            let skipped = history.len().saturating_sub(last);
+            history.drain(..skipped);
+        }
+
+        history
+    }
 }
 
 pub async fn get_history() -> Result<Vec<CommandHistory>, String> {
@@ -77,3 +910,7 @@ pub async fn get_history() -> Result<Vec<CommandHistory>, String> {
         Err(e) => Err(e),
     }
 }
+
+pub async fn search_history(filter: &HistoryFilter) -> Result<Vec<CommandHistory>, String> {
+    get_history().await.map(|history| filter.apply(history))
+}
@@ -0,0 +1,1379 @@
+#[derive(Debug, Clone, PartialEq)]
+pub struct Iso4217Currency {
+    pub code: &'static str,
+    pub numeric_code: Option<u16>,
+    pub minor_units: Option<u32>,
+    pub name: &'static str,
+    pub symbol: &'static str,
+    pub narrow_symbol: &'static str,
+}
+
+// Codes without a numeric code are provider specific (e.g. FOK, GGP, KID)
+pub const ISO_4217_CURRENCIES: &[Iso4217Currency] = &[
+    Iso4217Currency {
+        code: "AED",
+        numeric_code: Some(784),
+        minor_units: Some(2),
+        name: "UAE Dirham",
+        symbol: "AED",
+        narrow_symbol: "د.إ",
+    },
+    Iso4217Currency {
+        code: "AFN",
+        numeric_code: Some(971),
+        minor_units: Some(2),
+        name: "Afghan Afghani",
+        symbol: "AFN",
+        narrow_symbol: "؋",
+    },
+    Iso4217Currency {
+        code: "ALL",
+        numeric_code: Some(8),
+        minor_units: Some(2),
+        name: "Albanian Lek",
+        symbol: "ALL",
+        narrow_symbol: "L",
+    },
+    Iso4217Currency {
+        code: "AMD",
+        numeric_code: Some(51),
+        minor_units: Some(2),
+        name: "Armenian Dram",
+        symbol: "AMD",
+        narrow_symbol: "֏",
+    },
+    Iso4217Currency {
+        code: "ANG",
+        numeric_code: Some(532),
+        minor_units: Some(2),
+        name: "Netherlands Antillean Guilder",
+        symbol: "ANG",
+        narrow_symbol: "ƒ",
+    },
+    Iso4217Currency {
+        code: "AOA",
+        numeric_code: Some(973),
+        minor_units: Some(2),
+        name: "Angolan Kwanza",
+        symbol: "AOA",
+        narrow_symbol: "Kz",
+    },
+    Iso4217Currency {
+        code: "ARS",
+        numeric_code: Some(32),
+        minor_units: Some(2),
+        name: "Argentine Peso",
+        symbol: "ARS",
+        narrow_symbol: "$",
+    },
+    Iso4217Currency {
+        code: "AUD",
+        numeric_code: Some(36),
+        minor_units: Some(2),
+        name: "Australian Dollar",
+        symbol: "A$",
+        narrow_symbol: "$",
+    },
+    Iso4217Currency {
+        code: "AWG",
+        numeric_code: Some(533),
+        minor_units: Some(2),
+        name: "Aruban Florin",
+        symbol: "AWG",
+        narrow_symbol: "ƒ",
+    },
+    Iso4217Currency {
+        code: "AZN",
+        numeric_code: Some(944),
+        minor_units: Some(2),
+        name: "Azerbaijani Manat",
+        symbol: "AZN",
+        narrow_symbol: "₼",
+    },
+    Iso4217Currency {
+        code: "BAM",
+        numeric_code: Some(977),
+        minor_units: Some(2),
+        name: "Bosnia-Herzegovina Convertible Mark",
+        symbol: "BAM",
+        narrow_symbol: "KM",
+    },
+    Iso4217Currency {
+        code: "BBD",
+        numeric_code: Some(52),
+        minor_units: Some(2),
+        name: "Barbadian Dollar",
+        symbol: "BBD",
+        narrow_symbol: "$",
+    },
+    Iso4217Currency {
+        code: "BDT",
+        numeric_code: Some(50),
+        minor_units: Some(2),
+        name: "Bangladeshi Taka",
+        symbol: "BDT",
+        narrow_symbol: "৳",
+    },
+    Iso4217Currency {
+        code: "BGN",
+        numeric_code: Some(975),
+        minor_units: Some(2),
+        name: "Bulgarian Lev",
+        symbol: "BGN",
+        narrow_symbol: "лв",
+    },
+    Iso4217Currency {
+        code: "BHD",
+        numeric_code: Some(48),
+        minor_units: Some(3),
+        name: "Bahraini Dinar",
+        symbol: "BHD",
+        narrow_symbol: ".د.ب",
+    },
+    Iso4217Currency {
+        code: "BIF",
+        numeric_code: Some(108),
+        minor_units: Some(0),
+        name: "Burundian Franc",
+        symbol: "BIF",
+        narrow_symbol: "FBu",
+    },
+    Iso4217Currency {
+        code: "BMD",
+        numeric_code: Some(60),
+        minor_units: Some(2),
+        name: "Bermudan Dollar",
+        symbol: "BMD",
+        narrow_symbol: "$",
+    },
+    Iso4217Currency {
+        code: "BND",
+        numeric_code: Some(96),
+        minor_units: Some(2),
+        name: "Brunei Dollar",
+        symbol: "BND",
+        narrow_symbol: "$",
+    },
+    Iso4217Currency {
+        code: "BOB",
+        numeric_code: Some(68),
+        minor_units: Some(2),
+        name: "Bolivian Boliviano",
+        symbol: "BOB",
+        narrow_symbol: "Bs",
+    },
+    Iso4217Currency {
+        code: "BRL",
+        numeric_code: Some(986),
+        minor_units: Some(2),
+        name: "Brazilian Real",
+        symbol: "R$",
+        narrow_symbol: "R$",
+    },
+    Iso4217Currency {
+        code: "BSD",
+        numeric_code: Some(44),
+        minor_units: Some(2),
+        name: "Bahamian Dollar",
+        symbol: "BSD",
+        narrow_symbol: "$",
+    },
+    Iso4217Currency {
+        code: "BTN",
+        numeric_code: Some(64),
+        minor_units: Some(2),
+        name: "Bhutanese Ngultrum",
+        symbol: "BTN",
+        narrow_symbol: "Nu.",
+    },
+    Iso4217Currency {
+        code: "BWP",
+        numeric_code: Some(72),
+        minor_units: Some(2),
+        name: "Botswanan Pula",
+        symbol: "BWP",
+        narrow_symbol: "P",
+    },
+    Iso4217Currency {
+        code: "BYN",
+        numeric_code: Some(933),
+        minor_units: Some(2),
+        name: "Belarusian Ruble",
+        symbol: "BYN",
+        narrow_symbol: "Br",
+    },
+    Iso4217Currency {
+        code: "BZD",
+        numeric_code: Some(84),
+        minor_units: Some(2),
+        name: "Belize Dollar",
+        symbol: "BZD",
+        narrow_symbol: "$",
+    },
+    Iso4217Currency {
+        code: "CAD",
+        numeric_code: Some(124),
+        minor_units: Some(2),
+        name: "Canadian Dollar",
+        symbol: "CA$",
+        narrow_symbol: "$",
+    },
+    Iso4217Currency {
+        code: "CDF",
+        numeric_code: Some(976),
+        minor_units: Some(2),
+        name: "Congolese Franc",
+        symbol: "CDF",
+        narrow_symbol: "FC",
+    },
+    Iso4217Currency {
+        code: "CHF",
+        numeric_code: Some(756),
+        minor_units: Some(2),
+        name: "Swiss Franc",
+        symbol: "CHF",
+        narrow_symbol: "CHF",
+    },
+    Iso4217Currency {
+        code: "CLF",
+        numeric_code: Some(990),
+        minor_units: Some(4),
+        name: "Chilean Unit of Account (UF)",
+        symbol: "CLF",
+        narrow_symbol: "UF",
+    },
+    Iso4217Currency {
+        code: "CLP",
+        numeric_code: Some(152),
+        minor_units: Some(0),
+        name: "Chilean Peso",
+        symbol: "CLP",
+        narrow_symbol: "$",
+    },
+    Iso4217Currency {
+        code: "CNY",
+        numeric_code: Some(156),
+        minor_units: Some(2),
+        name: "Chinese Yuan",
+        symbol: "CN¥",
+        narrow_symbol: "¥",
+    },
+    Iso4217Currency {
+        code: "COP",
+        numeric_code: Some(170),
+        minor_units: Some(2),
+        name: "Colombian Peso",
+        symbol: "COP",
+        narrow_symbol: "$",
+    },
+    Iso4217Currency {
+        code: "CRC",
+        numeric_code: Some(188),
+        minor_units: Some(2),
+        name: "Costa Rican Colón",
+        symbol: "CRC",
+        narrow_symbol: "₡",
+    },
+    Iso4217Currency {
+        code: "CUP",
+        numeric_code: Some(192),
+        minor_units: Some(2),
+        name: "Cuban Peso",
+        symbol: "CUP",
+        narrow_symbol: "$",
+    },
+    Iso4217Currency {
+        code: "CVE",
+        numeric_code: Some(132),
+        minor_units: Some(2),
+        name: "Cape Verdean Escudo",
+        symbol: "CVE",
+        narrow_symbol: "$",
+    },
+    Iso4217Currency {
+        code: "CZK",
+        numeric_code: Some(203),
+        minor_units: Some(2),
+        name: "Czech Koruna",
+        symbol: "CZK",
+        narrow_symbol: "Kč",
+    },
+    Iso4217Currency {
+        code: "DJF",
+        numeric_code: Some(262),
+        minor_units: Some(0),
+        name: "Djiboutian Franc",
+        symbol: "DJF",
+        narrow_symbol: "Fdj",
+    },
+    Iso4217Currency {
+        code: "DKK",
+        numeric_code: Some(208),
+        minor_units: Some(2),
+        name: "Danish Krone",
+        symbol: "DKK",
+        narrow_symbol: "kr",
+    },
+    Iso4217Currency {
+        code: "DOP",
+        numeric_code: Some(214),
+        minor_units: Some(2),
+        name: "Dominican Peso",
+        symbol: "DOP",
+        narrow_symbol: "$",
+    },
+    Iso4217Currency {
+        code: "DZD",
+        numeric_code: Some(12),
+        minor_units: Some(2),
+        name: "Algerian Dinar",
+        symbol: "DZD",
+        narrow_symbol: "د.ج",
+    },
+    Iso4217Currency {
+        code: "EGP",
+        numeric_code: Some(818),
+        minor_units: Some(2),
+        name: "Egyptian Pound",
+        symbol: "EGP",
+        narrow_symbol: "E£",
+    },
+    Iso4217Currency {
+        code: "ERN",
+        numeric_code: Some(232),
+        minor_units: Some(2),
+        name: "Eritrean Nakfa",
+        symbol: "ERN",
+        narrow_symbol: "Nfk",
+    },
+    Iso4217Currency {
+        code: "ETB",
+        numeric_code: Some(230),
+        minor_units: Some(2),
+        name: "Ethiopian Birr",
+        symbol: "ETB",
+        narrow_symbol: "Br",
+    },
+    Iso4217Currency {
+        code: "EUR",
+        numeric_code: Some(978),
+        minor_units: Some(2),
+        name: "Euro",
+        symbol: "€",
+        narrow_symbol: "€",
+    },
+    Iso4217Currency {
+        code: "FJD",
+        numeric_code: Some(242),
+        minor_units: Some(2),
+        name: "Fijian Dollar",
+        symbol: "FJD",
+        narrow_symbol: "$",
+    },
+    Iso4217Currency {
+        code: "FKP",
+        numeric_code: Some(238),
+        minor_units: Some(2),
+        name: "Falkland Islands Pound",
+        symbol: "FKP",
+        narrow_symbol: "£",
+    },
+    Iso4217Currency {
+        code: "FOK",
+        numeric_code: None,
+        minor_units: Some(2),
+        name: "Faroese Króna",
+        symbol: "FOK",
+        narrow_symbol: "kr",
+    },
+    Iso4217Currency {
+        code: "GBP",
+        numeric_code: Some(826),
+        minor_units: Some(2),
+        name: "British Pound",
+        symbol: "£",
+        narrow_symbol: "£",
+    },
+    Iso4217Currency {
+        code: "GEL",
+        numeric_code: Some(981),
+        minor_units: Some(2),
+        name: "Georgian Lari",
+        symbol: "GEL",
+        narrow_symbol: "₾",
+    },
+    Iso4217Currency {
+        code: "GGP",
+        numeric_code: None,
+        minor_units: Some(2),
+        name: "Guernsey Pound",
+        symbol: "GGP",
+        narrow_symbol: "£",
+    },
+    Iso4217Currency {
+        code: "GHS",
+        numeric_code: Some(936),
+        minor_units: Some(2),
+        name: "Ghanaian Cedi",
+        symbol: "GHS",
+        narrow_symbol: "GH₵",
+    },
+    Iso4217Currency {
+        code: "GIP",
+        numeric_code: Some(292),
+        minor_units: Some(2),
+        name: "Gibraltar Pound",
+        symbol: "GIP",
+        narrow_symbol: "£",
+    },
+    Iso4217Currency {
+        code: "GMD",
+        numeric_code: Some(270),
+        minor_units: Some(2),
+        name: "Gambian Dalasi",
+        symbol: "GMD",
+        narrow_symbol: "D",
+    },
+    Iso4217Currency {
+        code: "GNF",
+        numeric_code: Some(324),
+        minor_units: Some(0),
+        name: "Guinean Franc",
+        symbol: "GNF",
+        narrow_symbol: "FG",
+    },
+    Iso4217Currency {
+        code: "GTQ",
+        numeric_code: Some(320),
+        minor_units: Some(2),
+        name: "Guatemalan Quetzal",
+        symbol: "GTQ",
+        narrow_symbol: "Q",
+    },
+    Iso4217Currency {
+        code: "GYD",
+        numeric_code: Some(328),
+        minor_units: Some(2),
+        name: "Guyanaese Dollar",
+        symbol: "GYD",
+        narrow_symbol: "$",
+    },
+    Iso4217Currency {
+        code: "HKD",
+        numeric_code: Some(344),
+        minor_units: Some(2),
+        name: "Hong Kong Dollar",
+        symbol: "HK$",
+        narrow_symbol: "$",
+    },
+    Iso4217Currency {
+        code: "HNL",
+        numeric_code: Some(340),
+        minor_units: Some(2),
+        name: "Honduran Lempira",
+        symbol: "HNL",
+        narrow_symbol: "L",
+    },
+    Iso4217Currency {
+        code: "HRK",
+        numeric_code: Some(191),
+        minor_units: Some(2),
+        name: "Croatian Kuna",
+        symbol: "HRK",
+        narrow_symbol: "kn",
+    },
+    Iso4217Currency {
+        code: "HTG",
+        numeric_code: Some(332),
+        minor_units: Some(2),
+        name: "Haitian Gourde",
+        symbol: "HTG",
+        narrow_symbol: "G",
+    },
+    Iso4217Currency {
+        code: "HUF",
+        numeric_code: Some(348),
+        minor_units: Some(2),
+        name: "Hungarian Forint",
+        symbol: "HUF",
+        narrow_symbol: "Ft",
+    },
+    Iso4217Currency {
+        code: "IDR",
+        numeric_code: Some(360),
+        minor_units: Some(2),
+        name: "Indonesian Rupiah",
+        symbol: "IDR",
+        narrow_symbol: "Rp",
+    },
+    Iso4217Currency {
+        code: "ILS",
+        numeric_code: Some(376),
+        minor_units: Some(2),
+        name: "Israeli New Shekel",
+        symbol: "₪",
+        narrow_symbol: "₪",
+    },
+    Iso4217Currency {
+        code: "IMP",
+        numeric_code: None,
+        minor_units: Some(2),
+        name: "Manx Pound",
+        symbol: "IMP",
+        narrow_symbol: "£",
+    },
+    Iso4217Currency {
+        code: "INR",
+        numeric_code: Some(356),
+        minor_units: Some(2),
+        name: "Indian Rupee",
+        symbol: "₹",
+        narrow_symbol: "₹",
+    },
+    Iso4217Currency {
+        code: "IQD",
+        numeric_code: Some(368),
+        minor_units: Some(3),
+        name: "Iraqi Dinar",
+        symbol: "IQD",
+        narrow_symbol: "ع.د",
+    },
+    Iso4217Currency {
+        code: "IRR",
+        numeric_code: Some(364),
+        minor_units: Some(2),
+        name: "Iranian Rial",
+        symbol: "IRR",
+        narrow_symbol: "﷼",
+    },
+    Iso4217Currency {
+        code: "ISK",
+        numeric_code: Some(352),
+        minor_units: Some(0),
+        name: "Icelandic Króna",
+        symbol: "ISK",
+        narrow_symbol: "kr",
+    },
+    Iso4217Currency {
+        code: "JEP",
+        numeric_code: None,
+        minor_units: Some(2),
+        name: "Jersey Pound",
+        symbol: "JEP",
+        narrow_symbol: "£",
+    },
+    Iso4217Currency {
+        code: "JMD",
+        numeric_code: Some(388),
+        minor_units: Some(2),
+        name: "Jamaican Dollar",
+        symbol: "JMD",
+        narrow_symbol: "$",
+    },
+    Iso4217Currency {
+        code: "JOD",
+        numeric_code: Some(400),
+        minor_units: Some(3),
+        name: "Jordanian Dinar",
+        symbol: "JOD",
+        narrow_symbol: "د.ا",
+    },
+    Iso4217Currency {
+        code: "JPY",
+        numeric_code: Some(392),
+        minor_units: Some(0),
+        name: "Japanese Yen",
+        symbol: "¥",
+        narrow_symbol: "¥",
+    },
+    Iso4217Currency {
+        code: "KES",
+        numeric_code: Some(404),
+        minor_units: Some(2),
+        name: "Kenyan Shilling",
+        symbol: "KES",
+        narrow_symbol: "KSh",
+    },
+    Iso4217Currency {
+        code: "KGS",
+        numeric_code: Some(417),
+        minor_units: Some(2),
+        name: "Kyrgystani Som",
+        symbol: "KGS",
+        narrow_symbol: "⃀",
+    },
+    Iso4217Currency {
+        code: "KHR",
+        numeric_code: Some(116),
+        minor_units: Some(2),
+        name: "Cambodian Riel",
+        symbol: "KHR",
+        narrow_symbol: "៛",
+    },
+    Iso4217Currency {
+        code: "KID",
+        numeric_code: None,
+        minor_units: Some(2),
+        name: "Kiribati Dollar",
+        symbol: "KID",
+        narrow_symbol: "$",
+    },
+    Iso4217Currency {
+        code: "KMF",
+        numeric_code: Some(174),
+        minor_units: Some(0),
+        name: "Comorian Franc",
+        symbol: "KMF",
+        narrow_symbol: "CF",
+    },
+    Iso4217Currency {
+        code: "KRW",
+        numeric_code: Some(410),
+        minor_units: Some(0),
+        name: "South Korean Won",
+        symbol: "₩",
+        narrow_symbol: "₩",
+    },
+    Iso4217Currency {
+        code: "KWD",
+        numeric_code: Some(414),
+        minor_units: Some(3),
+        name: "Kuwaiti Dinar",
+        symbol: "KWD",
+        narrow_symbol: "د.ك",
+    },
+    Iso4217Currency {
+        code: "KYD",
+        numeric_code: Some(136),
+        minor_units: Some(2),
+        name: "Cayman Islands Dollar",
+        symbol: "KYD",
+        narrow_symbol: "$",
+    },
+    Iso4217Currency {
+        code: "KZT",
+        numeric_code: Some(398),
+        minor_units: Some(2),
+        name: "Kazakhstani Tenge",
+        symbol: "KZT",
+        narrow_symbol: "₸",
+    },
+    Iso4217Currency {
+        code: "LAK",
+        numeric_code: Some(418),
+        minor_units: Some(2),
+        name: "Laotian Kip",
+        symbol: "LAK",
+        narrow_symbol: "₭",
+    },
+    Iso4217Currency {
+        code: "LBP",
+        numeric_code: Some(422),
+        minor_units: Some(2),
+        name: "Lebanese Pound",
+        symbol: "LBP",
+        narrow_symbol: "ل.ل",
+    },
+    Iso4217Currency {
+        code: "LKR",
+        numeric_code: Some(144),
+        minor_units: Some(2),
+        name: "Sri Lankan Rupee",
+        symbol: "LKR",
+        narrow_symbol: "Rs",
+    },
+    Iso4217Currency {
+        code: "LRD",
+        numeric_code: Some(430),
+        minor_units: Some(2),
+        name: "Liberian Dollar",
+        symbol: "LRD",
+        narrow_symbol: "$",
+    },
+    Iso4217Currency {
+        code: "LSL",
+        numeric_code: Some(426),
+        minor_units: Some(2),
+        name: "Lesotho Loti",
+        symbol: "LSL",
+        narrow_symbol: "L",
+    },
+    Iso4217Currency {
+        code: "LYD",
+        numeric_code: Some(434),
+        minor_units: Some(3),
+        name: "Libyan Dinar",
+        symbol: "LYD",
+        narrow_symbol: "ل.د",
+    },
+    Iso4217Currency {
+        code: "MAD",
+        numeric_code: Some(504),
+        minor_units: Some(2),
+        name: "Moroccan Dirham",
+        symbol: "MAD",
+        narrow_symbol: "د.م.",
+    },
+    Iso4217Currency {
+        code: "MDL",
+        numeric_code: Some(498),
+        minor_units: Some(2),
+        name: "Moldovan Leu",
+        symbol: "MDL",
+        narrow_symbol: "L",
+    },
+    Iso4217Currency {
+        code: "MGA",
+        numeric_code: Some(969),
+        minor_units: Some(2),
+        name: "Malagasy Ariary",
+        symbol: "MGA",
+        narrow_symbol: "Ar",
+    },
+    Iso4217Currency {
+        code: "MKD",
+        numeric_code: Some(807),
+        minor_units: Some(2),
+        name: "Macedonian Denar",
+        symbol: "MKD",
+        narrow_symbol: "ден",
+    },
+    Iso4217Currency {
+        code: "MMK",
+        numeric_code: Some(104),
+        minor_units: Some(2),
+        name: "Myanmar Kyat",
+        symbol: "MMK",
+        narrow_symbol: "K",
+    },
+    Iso4217Currency {
+        code: "MNT",
+        numeric_code: Some(496),
+        minor_units: Some(2),
+        name: "Mongolian Tugrik",
+        symbol: "MNT",
+        narrow_symbol: "₮",
+    },
+    Iso4217Currency {
+        code: "MOP",
+        numeric_code: Some(446),
+        minor_units: Some(2),
+        name: "Macanese Pataca",
+        symbol: "MOP",
+        narrow_symbol: "MOP$",
+    },
+    Iso4217Currency {
+        code: "MRU",
+        numeric_code: Some(929),
+        minor_units: Some(2),
+        name: "Mauritanian Ouguiya",
+        symbol: "MRU",
+        narrow_symbol: "UM",
+    },
+    Iso4217Currency {
+        code: "MUR",
+        numeric_code: Some(480),
+        minor_units: Some(2),
+        name: "Mauritian Rupee",
+        symbol: "MUR",
+        narrow_symbol: "Rs",
+    },
+    Iso4217Currency {
+        code: "MVR",
+        numeric_code: Some(462),
+        minor_units: Some(2),
+        name: "Maldivian Rufiyaa",
+        symbol: "MVR",
+        narrow_symbol: "Rf",
+    },
+    Iso4217Currency {
+        code: "MWK",
+        numeric_code: Some(454),
+        minor_units: Some(2),
+        name: "Malawian Kwacha",
+        symbol: "MWK",
+        narrow_symbol: "MK",
+    },
+    Iso4217Currency {
+        code: "MXN",
+        numeric_code: Some(484),
+        minor_units: Some(2),
+        name: "Mexican Peso",
+        symbol: "MX$",
+        narrow_symbol: "$",
+    },
+    Iso4217Currency {
+        code: "MYR",
+        numeric_code: Some(458),
+        minor_units: Some(2),
+        name: "Malaysian Ringgit",
+        symbol: "MYR",
+        narrow_symbol: "RM",
+    },
+    Iso4217Currency {
+        code: "MZN",
+        numeric_code: Some(943),
+        minor_units: Some(2),
+        name: "Mozambican Metical",
+        symbol: "MZN",
+        narrow_symbol: "MT",
+    },
+    Iso4217Currency {
+        code: "NAD",
+        numeric_code: Some(516),
+        minor_units: Some(2),
+        name: "Namibian Dollar",
+        symbol: "NAD",
+        narrow_symbol: "$",
+    },
+    Iso4217Currency {
+        code: "NGN",
+        numeric_code: Some(566),
+        minor_units: Some(2),
+        name: "Nigerian Naira",
+        symbol: "NGN",
+        narrow_symbol: "₦",
+    },
+    Iso4217Currency {
+        code: "NIO",
+        numeric_code: Some(558),
+        minor_units: Some(2),
+        name: "Nicaraguan Córdoba",
+        symbol: "NIO",
+        narrow_symbol: "C$",
+    },
+    Iso4217Currency {
+        code: "NOK",
+        numeric_code: Some(578),
+        minor_units: Some(2),
+        name: "Norwegian Krone",
+        symbol: "NOK",
+        narrow_symbol: "kr",
+    },
+    Iso4217Currency {
+        code: "NPR",
+        numeric_code: Some(524),
+        minor_units: Some(2),
+        name: "Nepalese Rupee",
+        symbol: "NPR",
+        narrow_symbol: "Rs",
+    },
+    Iso4217Currency {
+        code: "NZD",
+        numeric_code: Some(554),
+        minor_units: Some(2),
+        name: "New Zealand Dollar",
+        symbol: "NZ$",
+        narrow_symbol: "$",
+    },
+    Iso4217Currency {
+        code: "OMR",
+        numeric_code: Some(512),
+        minor_units: Some(3),
+        name: "Omani Rial",
+        symbol: "OMR",
+        narrow_symbol: "ر.ع.",
+    },
+    Iso4217Currency {
+        code: "PAB",
+        numeric_code: Some(590),
+        minor_units: Some(2),
+        name: "Panamanian Balboa",
+        symbol: "PAB",
+        narrow_symbol: "B/.",
+    },
+    Iso4217Currency {
+        code: "PEN",
+        numeric_code: Some(604),
+        minor_units: Some(2),
+        name: "Peruvian Sol",
+        symbol: "PEN",
+        narrow_symbol: "S/",
+    },
+    Iso4217Currency {
+        code: "PGK",
+        numeric_code: Some(598),
+        minor_units: Some(2),
+        name: "Papua New Guinean Kina",
+        symbol: "PGK",
+        narrow_symbol: "K",
+    },
+    Iso4217Currency {
+        code: "PHP",
+        numeric_code: Some(608),
+        minor_units: Some(2),
+        name: "Philippine Peso",
+        symbol: "₱",
+        narrow_symbol: "₱",
+    },
+    Iso4217Currency {
+        code: "PKR",
+        numeric_code: Some(586),
+        minor_units: Some(2),
+        name: "Pakistani Rupee",
+        symbol: "PKR",
+        narrow_symbol: "Rs",
+    },
+    Iso4217Currency {
+        code: "PLN",
+        numeric_code: Some(985),
+        minor_units: Some(2),
+        name: "Polish Zloty",
+        symbol: "PLN",
+        narrow_symbol: "zł",
+    },
+    Iso4217Currency {
+        code: "PYG",
+        numeric_code: Some(600),
+        minor_units: Some(0),
+        name: "Paraguayan Guarani",
+        symbol: "PYG",
+        narrow_symbol: "₲",
+    },
+    Iso4217Currency {
+        code: "QAR",
+        numeric_code: Some(634),
+        minor_units: Some(2),
+        name: "Qatari Riyal",
+        symbol: "QAR",
+        narrow_symbol: "ر.ق",
+    },
+    Iso4217Currency {
+        code: "RON",
+        numeric_code: Some(946),
+        minor_units: Some(2),
+        name: "Romanian Leu",
+        symbol: "RON",
+        narrow_symbol: "lei",
+    },
+    Iso4217Currency {
+        code: "RSD",
+        numeric_code: Some(941),
+        minor_units: Some(2),
+        name: "Serbian Dinar",
+        symbol: "RSD",
+        narrow_symbol: "дин.",
+    },
+    Iso4217Currency {
+        code: "RUB",
+        numeric_code: Some(643),
+        minor_units: Some(2),
+        name: "Russian Ruble",
+        symbol: "RUB",
+        narrow_symbol: "₽",
+    },
+    Iso4217Currency {
+        code: "RWF",
+        numeric_code: Some(646),
+        minor_units: Some(0),
+        name: "Rwandan Franc",
+        symbol: "RWF",
+        narrow_symbol: "RF",
+    },
+    Iso4217Currency {
+        code: "SAR",
+        numeric_code: Some(682),
+        minor_units: Some(2),
+        name: "Saudi Riyal",
+        symbol: "SAR",
+        narrow_symbol: "ر.س",
+    },
+    Iso4217Currency {
+        code: "SBD",
+        numeric_code: Some(90),
+        minor_units: Some(2),
+        name: "Solomon Islands Dollar",
+        symbol: "SBD",
+        narrow_symbol: "$",
+    },
+    Iso4217Currency {
+        code: "SCR",
+        numeric_code: Some(690),
+        minor_units: Some(2),
+        name: "Seychellois Rupee",
+        symbol: "SCR",
+        narrow_symbol: "Rs",
+    },
+    Iso4217Currency {
+        code: "SDG",
+        numeric_code: Some(938),
+        minor_units: Some(2),
+        name: "Sudanese Pound",
+        symbol: "SDG",
+        narrow_symbol: "ج.س.",
+    },
+    Iso4217Currency {
+        code: "SEK",
+        numeric_code: Some(752),
+        minor_units: Some(2),
+        name: "Swedish Krona",
+        symbol: "SEK",
+        narrow_symbol: "kr",
+    },
+    Iso4217Currency {
+        code: "SGD",
+        numeric_code: Some(702),
+        minor_units: Some(2),
+        name: "Singapore Dollar",
+        symbol: "SGD",
+        narrow_symbol: "$",
+    },
+    Iso4217Currency {
+        code: "SHP",
+        numeric_code: Some(654),
+        minor_units: Some(2),
+        name: "St. Helena Pound",
+        symbol: "SHP",
+        narrow_symbol: "£",
+    },
+    Iso4217Currency {
+        code: "SLE",
+        numeric_code: Some(925),
+        minor_units: Some(2),
+        name: "Sierra Leonean Leone",
+        symbol: "SLE",
+        narrow_symbol: "Le",
+    },
+    Iso4217Currency {
+        code: "SLL",
+        numeric_code: Some(694),
+        minor_units: Some(2),
+        name: "Sierra Leonean Leone (1964—2022)",
+        symbol: "SLL",
+        narrow_symbol: "Le",
+    },
+    Iso4217Currency {
+        code: "SOS",
+        numeric_code: Some(706),
+        minor_units: Some(2),
+        name: "Somali Shilling",
+        symbol: "SOS",
+        narrow_symbol: "Sh",
+    },
+    Iso4217Currency {
+        code: "SRD",
+        numeric_code: Some(968),
+        minor_units: Some(2),
+        name: "Surinamese Dollar",
+        symbol: "SRD",
+        narrow_symbol: "$",
+    },
+    Iso4217Currency {
+        code: "SSP",
+        numeric_code: Some(728),
+        minor_units: Some(2),
+        name: "South Sudanese Pound",
+        symbol: "SSP",
+        narrow_symbol: "£",
+    },
+    Iso4217Currency {
+        code: "STN",
+        numeric_code: Some(930),
+        minor_units: Some(2),
+        name: "São Tomé & Príncipe Dobra",
+        symbol: "STN",
+        narrow_symbol: "Db",
+    },
+    Iso4217Currency {
+        code: "SYP",
+        numeric_code: Some(760),
+        minor_units: Some(2),
+        name: "Syrian Pound",
+        symbol: "SYP",
+        narrow_symbol: "£",
+    },
+    Iso4217Currency {
+        code: "SZL",
+        numeric_code: Some(748),
+        minor_units: Some(2),
+        name: "Swazi Lilangeni",
+        symbol: "SZL",
+        narrow_symbol: "E",
+    },
+    Iso4217Currency {
+        code: "THB",
+        numeric_code: Some(764),
+        minor_units: Some(2),
+        name: "Thai Baht",
+        symbol: "THB",
+        narrow_symbol: "฿",
+    },
+    Iso4217Currency {
+        code: "TJS",
+        numeric_code: Some(972),
+        minor_units: Some(2),
+        name: "Tajikistani Somoni",
+        symbol: "TJS",
+        narrow_symbol: "SM",
+    },
+    Iso4217Currency {
+        code: "TMT",
+        numeric_code: Some(934),
+        minor_units: Some(2),
+        name: "Turkmenistani Manat",
+        symbol: "TMT",
+        narrow_symbol: "m",
+    },
+    Iso4217Currency {
+        code: "TND",
+        numeric_code: Some(788),
+        minor_units: Some(3),
+        name: "Tunisian Dinar",
+        symbol: "TND",
+        narrow_symbol: "د.ت",
+    },
+    Iso4217Currency {
+        code: "TOP",
+        numeric_code: Some(776),
+        minor_units: Some(2),
+        name: "Tongan Paʻanga",
+        symbol: "TOP",
+        narrow_symbol: "T$",
+    },
+    Iso4217Currency {
+        code: "TRY",
+        numeric_code: Some(949),
+        minor_units: Some(2),
+        name: "Turkish Lira",
+        symbol: "TRY",
+        narrow_symbol: "₺",
+    },
+    Iso4217Currency {
+        code: "TTD",
+        numeric_code: Some(780),
+        minor_units: Some(2),
+        name: "Trinidad & Tobago Dollar",
+        symbol: "TTD",
+        narrow_symbol: "$",
+    },
+    Iso4217Currency {
+        code: "TVD",
+        numeric_code: None,
+        minor_units: Some(2),
+        name: "Tuvaluan Dollar",
+        symbol: "TVD",
+        narrow_symbol: "$",
+    },
+    Iso4217Currency {
+        code: "TWD",
+        numeric_code: Some(901),
+        minor_units: Some(2),
+        name: "New Taiwan Dollar",
+        symbol: "NT$",
+        narrow_symbol: "$",
+    },
+    Iso4217Currency {
+        code: "TZS",
+        numeric_code: Some(834),
+        minor_units: Some(2),
+        name: "Tanzanian Shilling",
+        symbol: "TZS",
+        narrow_symbol: "TSh",
+    },
+    Iso4217Currency {
+        code: "UAH",
+        numeric_code: Some(980),
+        minor_units: Some(2),
+        name: "Ukrainian Hryvnia",
+        symbol: "UAH",
+        narrow_symbol: "₴",
+    },
+    Iso4217Currency {
+        code: "UGX",
+        numeric_code: Some(800),
+        minor_units: Some(0),
+        name: "Ugandan Shilling",
+        symbol: "UGX",
+        narrow_symbol: "USh",
+    },
+    Iso4217Currency {
+        code: "USD",
+        numeric_code: Some(840),
+        minor_units: Some(2),
+        name: "US Dollar",
+        symbol: "$",
+        narrow_symbol: "$",
+    },
+    Iso4217Currency {
+        code: "UYU",
+        numeric_code: Some(858),
+        minor_units: Some(2),
+        name: "Uruguayan Peso",
+        symbol: "UYU",
+        narrow_symbol: "$",
+    },
+    Iso4217Currency {
+        code: "UZS",
+        numeric_code: Some(860),
+        minor_units: Some(2),
+        name: "Uzbekistani Som",
+        symbol: "UZS",
+        narrow_symbol: "soʻm",
+    },
+    Iso4217Currency {
+        code: "VES",
+        numeric_code: Some(928),
+        minor_units: Some(2),
+        name: "Venezuelan Bolívar",
+        symbol: "VES",
+        narrow_symbol: "Bs.S",
+    },
+    Iso4217Currency {
+        code: "VND",
+        numeric_code: Some(704),
+        minor_units: Some(0),
+        name: "Vietnamese Dong",
+        symbol: "₫",
+        narrow_symbol: "₫",
+    },
+    Iso4217Currency {
+        code: "VUV",
+        numeric_code: Some(548),
+        minor_units: Some(0),
+        name: "Vanuatu Vatu",
+        symbol: "VUV",
+        narrow_symbol: "VT",
+    },
+    Iso4217Currency {
+        code: "WST",
+        numeric_code: Some(882),
+        minor_units: Some(2),
+        name: "Samoan Tala",
+        symbol: "WST",
+        narrow_symbol: "T",
+    },
+    Iso4217Currency {
+        code: "XAF",
+        numeric_code: Some(950),
+        minor_units: Some(0),
+        name: "Central African CFA Franc",
+        symbol: "FCFA",
+        narrow_symbol: "FCFA",
+    },
+    Iso4217Currency {
+        code: "XCD",
+        numeric_code: Some(951),
+        minor_units: Some(2),
+        name: "East Caribbean Dollar",
+        symbol: "EC$",
+        narrow_symbol: "$",
+    },
+    Iso4217Currency {
+        code: "XDR",
+        numeric_code: Some(960),
+        minor_units: None,
+        name: "Special Drawing Rights",
+        symbol: "XDR",
+        narrow_symbol: "XDR",
+    },
+    Iso4217Currency {
+        code: "XOF",
+        numeric_code: Some(952),
+        minor_units: Some(0),
+        name: "West African CFA Franc",
+        symbol: "F CFA",
+        narrow_symbol: "F CFA",
+    },
+    Iso4217Currency {
+        code: "XPF",
+        numeric_code: Some(953),
+        minor_units: Some(0),
+        name: "CFP Franc",
+        symbol: "CFPF",
+        narrow_symbol: "₣",
+    },
+    Iso4217Currency {
+        code: "YER",
+        numeric_code: Some(886),
+        minor_units: Some(2),
+        name: "Yemeni Rial",
+        symbol: "YER",
+        narrow_symbol: "﷼",
+    },
+    Iso4217Currency {
+        code: "ZAR",
+        numeric_code: Some(710),
+        minor_units: Some(2),
+        name: "South African Rand",
+        symbol: "ZAR",
+        narrow_symbol: "R",
+    },
+    Iso4217Currency {
+        code: "ZMW",
+        numeric_code: Some(967),
+        minor_units: Some(2),
+        name: "Zambian Kwacha",
+        symbol: "ZMW",
+        narrow_symbol: "ZK",
+    },
+    Iso4217Currency {
+        code: "ZWG",
+        numeric_code: Some(924),
+        minor_units: Some(2),
+        name: "Zimbabwean Gold",
+        symbol: "ZWG",
+        narrow_symbol: "ZiG",
+    },
+    Iso4217Currency {
+        code: "ZWL",
+        numeric_code: Some(932),
+        minor_units: Some(2),
+        name: "Zimbabwean Dollar (2009—2024)",
+        symbol: "ZWL",
+        narrow_symbol: "Z$",
+    },
+];
+
+// symbols shared by several currencies resolve to the most traded one
+pub const PREFERRED_SYMBOLS: &[(&str, &str)] = &[
+    ("$", "USD"),
+    ("US$", "USD"),
+    ("€", "EUR"),
+    ("£", "GBP"),
+    ("¥", "JPY"),
+    ("円", "JPY"),
+    ("元", "CNY"),
+    ("zł", "PLN"),
+    ("Fr", "CHF"),
+    ("kr", "SEK"),
+    ("Kč", "CZK"),
+    ("Ft", "HUF"),
+    ("lei", "RON"),
+    ("Rs", "INR"),
+    ("R", "ZAR"),
+    ("C$", "CAD"),
+];
+
+pub fn find_iso_currency_by_symbol(symbol: &str) -> Option<&'static Iso4217Currency> {
+    let symbol = symbol.trim().to_lowercase();
+
+    if let Some((_, code)) = PREFERRED_SYMBOLS
+        .iter()
+        .find(|(preferred, _)| preferred.to_lowercase() == symbol)
+    {
+        return find_iso_currency(code);
+    }
+
+    if let Some(currency) = ISO_4217_CURRENCIES.iter().find(|currency| {
+        currency.symbol.to_lowercase() == symbol && currency.symbol != currency.code
+    }) {
+        return Some(currency);
+    }
+
+    let mut narrow_matches = ISO_4217_CURRENCIES
+        .iter()
+        .filter(|currency| currency.narrow_symbol.to_lowercase() == symbol);
+
+    match (narrow_matches.next(), narrow_matches.next()) {
+        (Some(currency), None) => Some(currency),
+        _ => None,
+    }
+}
+
+pub fn find_iso_currency(code: &str) -> Option<&'static Iso4217Currency> {
+    let code = code.to_uppercase();
+
+    ISO_4217_CURRENCIES
+        .iter()
+        .find(|currency| currency.code == code)
+}
@@ -1,14 +1,30 @@
 use std::collections::HashMap;
 
+use chrono::{DateTime, NaiveDate, Utc};
 use futures::future::join_all;
+use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
 
 use crate::{
-    api::{currencies::get_supported_currencies_from_api, exchange_rate::get_exchange_rates},
+    api::{
+        currencies::{get_cached_supported_currencies, get_supported_currencies_from_api},
+        exchange_rate::{get_exchange_rates, get_exchange_rates_at},
+    },
+    conversion::rounding::RoundingMode,
     error::print_info,
 };
 
+use self::iso4217::{
+    find_iso_currency, find_iso_currency_by_symbol, Iso4217Currency, ISO_4217_CURRENCIES,
+};
+
+pub mod countries;
 pub mod history;
+pub mod iso4217;
+pub mod search;
+pub mod snapshot;
+
+const DEFAULT_MINOR_UNITS: u32 = 2;
 
 #[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
 pub struct Currency {
@@ -23,18 +39,82 @@ impl Currency {
         }
     }
 
+    pub fn with_name(mut self, name: String) -> Currency {
+        self.name = Some(name);
+        self
+    }
+
+    pub fn new_from_symbol(symbol: &str) -> Option<Currency> {
+        find_iso_currency_by_symbol(symbol)
+            .map(|iso_currency| Currency::new_from_code(iso_currency.code.to_string()))
+    }
+
     pub fn get_code(&self) -> &String {
         &self.code
     }
 
-    pub fn get_name(&self) -> &String {
+    pub fn get_name(&self) -> &str {
         let name = self.name.as_ref();
         if let Some(name) = name {
             name
+        } else if let Some(iso_currency) = self.get_iso_metadata() {
+            iso_currency.name
         } else {
             &self.code
         }
     }
+
+    pub fn get_iso_metadata(&self) -> Option<&'static Iso4217Currency> {
+        find_iso_currency(&self.code)
+    }
+
+    pub fn get_minor_units(&self) -> u32 {
+        self.get_iso_metadata()
+            .and_then(|iso_currency| iso_currency.minor_units)
+            .unwrap_or(DEFAULT_MINOR_UNITS)
+    }
+
+    pub fn get_numeric_code(&self) -> Option<u16> {
+        self.get_iso_metadata()
+            .and_then(|iso_currency| iso_currency.numeric_code)
+    }
+
+    pub fn get_symbol(&self) -> &str {
+        match self.get_iso_metadata() {
+            Some(iso_currency) => iso_currency.symbol,
+            None => &self.code,
+        }
+    }
+
+    pub fn get_narrow_symbol(&self) -> &str {
+        match self.get_iso_metadata() {
+            Some(iso_currency) => iso_currency.narrow_symbol,
+            None => &self.code,
+        }
+    }
+
+    pub fn round_amount(&self, amount: Decimal) -> Decimal {
+        self.round_amount_with(amount, RoundingMode::default())
+    }
+
+    pub fn round_amount_with(&self, amount: Decimal, rounding: RoundingMode) -> Decimal {
+        rounding.round(amount, self.get_minor_units())
+    }
+
+    pub fn format_amount(&self, amount: Decimal) -> String {
+        self.round_amount(amount).to_string()
+    }
+
+    pub fn format_rate(&self, rate: Decimal) -> String {
+        let mut rate = rate.normalize();
+        let minor_units = self.get_minor_units();
+
+        if rate.scale() < minor_units {
+            rate.rescale(minor_units);
+        }
+
+        rate.to_string()
+    }
 }
 
 pub async fn get_supported_currencies() -> Result<Vec<Currency>, String> {
@@ -46,38 +126,150 @@ pub async fn get_supported_currencies() -> Result<Vec<Currency>, String> {
     }
 }
 
-pub async fn get_rate(source_currency: &str, target_currency: &str) -> Result<f64, String> {
+pub fn get_iso_currencies() -> Vec<Currency> {
+    ISO_4217_CURRENCIES
+        .iter()
+        .map(|iso_currency| {
+            Currency::new_from_code(iso_currency.code.to_string())
+                .with_name(iso_currency.name.to_string())
+        })
+        .collect()
+}
+
+// the embedded list covers the usual codes, the provider's list is only requested for the others
+pub async fn get_validation_currencies(codes: &[String]) -> Vec<Currency> {
+    if let Some(currencies) = get_cached_supported_currencies() {
+        return currencies;
+    }
+
+    let iso_currencies = get_iso_currencies();
+    let all_known = codes.iter().all(|code| {
+        iso_currencies
+            .iter()
+            .any(|currency| currency.get_code().eq_ignore_ascii_case(code))
+    });
+
+    if all_known {
+        return iso_currencies;
+    }
+
+    get_supported_currencies().await.unwrap_or(iso_currencies)
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct RateTable {
+    base: Currency,
+    rates: HashMap<String, Decimal>,
+    updated_at: Option<DateTime<Utc>>,
+}
+
+impl RateTable {
+    pub fn new(base: Currency, rates: HashMap<String, Decimal>) -> RateTable {
+        RateTable {
+            base,
+            rates,
+            updated_at: None,
+        }
+    }
+
+    pub fn with_updated_at(mut self, updated_at: Option<DateTime<Utc>>) -> RateTable {
+        self.updated_at = updated_at;
+        self
+    }
+
+    pub fn get_updated_at(&self) -> Option<DateTime<Utc>> {
+        self.updated_at
+    }
+
+    pub fn get_base(&self) -> &Currency {
+        &self.base
+    }
+
+    pub fn get_rates(&self) -> &HashMap<String, Decimal> {
+        &self.rates
+    }
+
+    pub fn get_rate(&self, target_currency: &str) -> Result<Decimal, String> {
+        let target_currency = target_currency.to_uppercase();
+
+        match self.rates.get(&target_currency) {
+            Some(rate) => Ok(*rate),
+            None => Err(format!("Exchange rate for {} not found", target_currency)),
+        }
+    }
+
+    // rate between two quoted currencies derived through the base currency
+    pub fn get_cross_rate(
+        &self,
+        source_currency: &str,
+        target_currency: &str,
+    ) -> Result<Decimal, String> {
+        let source_rate = self.get_rate_or_base(source_currency)?;
+        let target_rate = self.get_rate_or_base(target_currency)?;
+
+        if source_rate.is_zero() {
+            return Err(format!(
+                "Exchange rate for {} is zero",
+                source_currency.to_uppercase()
+            ));
+        }
+
+        match target_rate.checked_div(source_rate) {
+            Some(rate) => Ok(rate),
+            None => Err(format!(
+                "Exchange rate from {} to {} is out of range",
+                source_currency.to_uppercase(),
+                target_currency.to_uppercase()
+            )),
+        }
+    }
+
+    fn get_rate_or_base(&self, currency: &str) -> Result<Decimal, String> {
+        match currency.eq_ignore_ascii_case(self.base.get_code()) {
+            true => Ok(Decimal::ONE),
+            false => self.get_rate(currency),
+        }
+    }
+}
+
+pub async fn get_rate_table(source_currency: &str) -> Result<RateTable, String> {
+    get_rate_table_at(source_currency, None).await
+}
+
+pub async fn get_rate_table_at(
+    source_currency: &str,
+    date: Option<NaiveDate>,
+) -> Result<RateTable, String> {
     let source_currency = Currency::new_from_code(source_currency.to_string());
-    let target_currency = Currency::new_from_code(target_currency.to_string());
 
-    let rates = get_exchange_rates(source_currency).await;
+    let rates = get_exchange_rates_at(source_currency.clone(), date).await;
 
     match rates {
         Ok(rates) => {
-            let currency_key = target_currency.get_code();
-            let rate = rates.get(currency_key);
-
-            match rate {
-                Some(rate) => Ok(*rate),
-                None => Err(format!(
-                    "Exchange rate for {} not found",
-                    target_currency.get_code()
-                )),
-            }
+            Ok(RateTable::new(source_currency, rates.rates).with_updated_at(rates.updated_at))
         }
         Err(e) => Err(e),
     }
 }
 
+pub async fn get_rate(source_currency: &str, target_currency: &str) -> Result<Decimal, String> {
+    let rate_table = get_rate_table(source_currency).await;
+
+    match rate_table {
+        Ok(rate_table) => rate_table.get_rate(target_currency),
+        Err(e) => Err(e),
+    }
+}
+
 pub async fn get_supported_currencies_with_rates(
-) -> Result<Vec<HashMap<String, HashMap<String, f64>>>, String> {
+) -> Result<Vec<HashMap<String, HashMap<String, Decimal>>>, String> {
     let currencies = get_supported_currencies_from_api().await;
 
     print_info("Getting exchange rates for supported currencies...");
 
     match currencies {
         Ok(currencies) => {
-            let mut currencies_with_rates: Vec<HashMap<String, HashMap<String, f64>>> = vec![];
+            let mut currencies_with_rates: Vec<HashMap<String, HashMap<String, Decimal>>> = vec![];
 
             let get_rates_functions = currencies.iter().map(|currency| {
                 let currency_code = currency.get_code().clone();
@@ -0,0 +1,218 @@
+use std::fmt;
+
+use super::{
+    countries::COUNTRY_CURRENCIES, get_iso_currencies, get_supported_currencies,
+    iso4217::PREFERRED_SYMBOLS, Currency,
+};
+
+pub const CURRENCY_ALIASES: &[(&str, &str)] = &[
+    ("dollar", "USD"),
+    ("buck", "USD"),
+    ("bucks", "USD"),
+    ("greenback", "USD"),
+    ("euro", "EUR"),
+    ("euros", "EUR"),
+    ("pound", "GBP"),
+    ("pound sterling", "GBP"),
+    ("sterling", "GBP"),
+    ("quid", "GBP"),
+    ("yen", "JPY"),
+    ("yuan", "CNY"),
+    ("renminbi", "CNY"),
+    ("rmb", "CNY"),
+    ("franc", "CHF"),
+    ("swissie", "CHF"),
+    ("loonie", "CAD"),
+    ("aussie", "AUD"),
+    ("kiwi", "NZD"),
+    ("rupee", "INR"),
+    ("peso", "MXN"),
+    ("real", "BRL"),
+    ("reais", "BRL"),
+    ("zloty", "PLN"),
+    ("złoty", "PLN"),
+    ("won", "KRW"),
+    ("ruble", "RUB"),
+    ("rouble", "RUB"),
+    ("lira", "TRY"),
+    ("krona", "SEK"),
+    ("krone", "NOK"),
+    ("krone", "DKK"),
+    ("rand", "ZAR"),
+    ("shekel", "ILS"),
+    ("dirham", "AED"),
+    ("riyal", "SAR"),
+    ("baht", "THB"),
+    ("forint", "HUF"),
+    ("koruna", "CZK"),
+    ("ringgit", "MYR"),
+    ("rupiah", "IDR"),
+    ("dong", "VND"),
+    ("hryvnia", "UAH"),
+    ("naira", "NGN"),
+    ("leu", "RON"),
+    ("lev", "BGN"),
+];
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum MatchReason {
+    Code,
+    Symbol(String),
+    Alias(String),
+    Name,
+    Country(String),
+}
+
+impl fmt::Display for MatchReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MatchReason::Code => write!(f, "code"),
+            MatchReason::Symbol(symbol) => write!(f, "symbol {}", symbol),
+            MatchReason::Alias(alias) => write!(f, "alias {}", alias),
+            MatchReason::Name => write!(f, "name"),
+            MatchReason::Country(country) => write!(f, "country {}", country),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct CurrencyMatch {
+    pub currency: Currency,
+    pub score: u32,
+    pub reason: MatchReason,
+}
+
+// candidates matching the query, best matches first
+pub fn rank_currencies(query: &str, currencies: &[Currency]) -> Vec<CurrencyMatch> {
+    let query = query.trim().to_lowercase();
+
+    if query.is_empty() {
+        return vec![];
+    }
+
+    let mut matches: Vec<CurrencyMatch> = currencies
+        .iter()
+        .filter_map(|currency| {
+            [
+                match_code(&query, currency),
+                match_symbol(&query, currency),
+                match_alias(&query, currency),
+                match_name(&query, currency),
+                match_country(&query, currency),
+            ]
+            .into_iter()
+            .flatten()
+            .max_by_key(|(score, _)| *score)
+            .map(|(score, reason)| CurrencyMatch {
+                currency: currency.clone(),
+                score,
+                reason,
+            })
+        })
+        .collect();
+
+    matches.sort_by(|a, b| {
+        b.score
+            .cmp(&a.score)
+            .then_with(|| a.currency.get_code().cmp(b.currency.get_code()))
+    });
+
+    matches
+}
+
+pub async fn search_currencies(query: &str) -> Vec<CurrencyMatch> {
+    let currencies = get_supported_currencies()
+        .await
+        .unwrap_or_else(|_| get_iso_currencies());
+
+    rank_currencies(query, &currencies)
+}
+
+fn match_code(query: &str, currency: &Currency) -> Option<(u32, MatchReason)> {
+    let code = currency.get_code().to_lowercase();
+
+    if code == query {
+        return Some((100, MatchReason::Code));
+    }
+
+    match query.len() < code.len() && code.starts_with(query) {
+        true => Some((50, MatchReason::Code)),
+        false => None,
+    }
+}
+
+fn match_symbol(query: &str, currency: &Currency) -> Option<(u32, MatchReason)> {
+    let code = currency.get_code();
+
+    if let Some((symbol, _)) = PREFERRED_SYMBOLS
+        .iter()
+        .find(|(symbol, preferred)| symbol.to_lowercase() == query && preferred == code)
+    {
+        return Some((95, MatchReason::Symbol(symbol.to_string())));
+    }
+
+    [currency.get_symbol(), currency.get_narrow_symbol()]
+        .into_iter()
+        .find(|symbol| symbol.to_lowercase() == query && symbol != code)
+        .map(|symbol| (90, MatchReason::Symbol(symbol.to_string())))
+}
+
+fn match_alias(query: &str, currency: &Currency) -> Option<(u32, MatchReason)> {
+    let aliases = CURRENCY_ALIASES
+        .iter()
+        .filter(|(_, code)| code == currency.get_code());
+
+    for (alias, _) in aliases.clone() {
+        if *alias == query {
+            return Some((85, MatchReason::Alias(alias.to_string())));
+        }
+    }
+
+    aliases
+        .filter(|(alias, _)| query.len() >= 3 && alias.starts_with(query))
+        .map(|(alias, _)| (45, MatchReason::Alias(alias.to_string())))
+        .next()
+}
+
+fn match_name(query: &str, currency: &Currency) -> Option<(u32, MatchReason)> {
+    let name = currency.get_name().to_lowercase();
+
+    if name == query {
+        return Some((90, MatchReason::Name));
+    }
+
+    if name.starts_with(query) {
+        return Some((65, MatchReason::Name));
+    }
+
+    if name.split_whitespace().any(|word| word.starts_with(query)) {
+        return Some((60, MatchReason::Name));
+    }
+
+    match query.len() >= 3 && name.contains(query) {
+        true => Some((40, MatchReason::Name)),
+        false => None,
+    }
+}
+
+fn match_country(query: &str, currency: &Currency) -> Option<(u32, MatchReason)> {
+    COUNTRY_CURRENCIES
+        .iter()
+        .filter(|(_, code)| code == currency.get_code())
+        .filter_map(|(country, _)| {
+            let name = country.to_lowercase();
+
+            let score = if name == query {
+                80
+            } else if name.split_whitespace().any(|word| word.starts_with(query)) {
+                55
+            } else if query.len() >= 3 && name.contains(query) {
+                35
+            } else {
+                return None;
+            };
+
+            Some((score, MatchReason::Country(country.to_string())))
+        })
+        .max_by_key(|(score, _)| *score)
+}
@@ -0,0 +1,88 @@
+use std::collections::HashMap;
+
+use chrono::Utc;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+use crate::cache::{
+    file_cache::{create_cache_file, read_and_invalid_cache_file},
+    CacheConfigs,
+};
+
+use super::{Currency, RateTable};
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct RateSnapshot {
+    base: String,
+    taken_at: String,
+    rates: HashMap<String, Decimal>,
+}
+
+impl RateSnapshot {
+    pub fn new(rate_table: &RateTable) -> RateSnapshot {
+        let taken_at = rate_table.get_updated_at().unwrap_or_else(Utc::now);
+
+        RateSnapshot {
+            base: rate_table.get_base().get_code().clone(),
+            taken_at: taken_at.to_rfc3339(),
+            rates: rate_table.get_rates().clone(),
+        }
+    }
+
+    pub fn get_base(&self) -> &String {
+        &self.base
+    }
+
+    pub fn get_taken_at(&self) -> &String {
+        &self.taken_at
+    }
+
+    pub fn get_rate_table(&self) -> RateTable {
+        RateTable::new(
+            Currency::new_from_code(self.base.clone()),
+            self.rates.clone(),
+        )
+    }
+}
+
+// the two most recent distinct rate sets of a base currency
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Default)]
+pub struct RateSnapshots {
+    latest: Option<RateSnapshot>,
+    previous: Option<RateSnapshot>,
+}
+
+impl RateSnapshots {
+    pub fn load(base: &Currency) -> RateSnapshots {
+        read_and_invalid_cache_file(CacheConfigs::RateSnapshots, Some(base.clone()))
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self, base: &Currency) -> Result<(), String> {
+        match create_cache_file(self, CacheConfigs::RateSnapshots, Some(base.clone())) {
+            Ok(_) => Ok(()),
+            Err(e) => Err(e.to_string()),
+        }
+    }
+
+    // stores the rates when they changed and returns the snapshot they should be compared with
+    pub fn record(&mut self, rate_table: &RateTable) -> Option<RateSnapshot> {
+        match &self.latest {
+            Some(latest) if latest.rates == *rate_table.get_rates() => (),
+            _ => {
+                self.previous = self.latest.take();
+                self.latest = Some(RateSnapshot::new(rate_table));
+            }
+        }
+
+        self.previous.clone()
+    }
+
+    pub fn get_latest(&self) -> Option<&RateSnapshot> {
+        self.latest.as_ref()
+    }
+
+    pub fn get_previous(&self) -> Option<&RateSnapshot> {
+        self.previous.as_ref()
+    }
+}
@@ -1,5 +1,17 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+
 use colored::Colorize;
 
+static VERBOSE: AtomicBool = AtomicBool::new(false);
+
+pub fn set_verbose(verbose: bool) {
+    VERBOSE.store(verbose, Ordering::Relaxed);
+}
+
+pub fn is_verbose() -> bool {
+    VERBOSE.load(Ordering::Relaxed)
+}
+
 pub fn print_error(message: &str) {
     println!("{} {}", "Error:".red(), message.red());
 }
@@ -8,6 +20,14 @@ pub fn print_warning(message: &str) {
     println!("{} {}", "Warning:".yellow(), message.yellow());
 }
 
+// informational messages go to stderr to keep the output of commands machine-readable
 pub fn print_info(message: &str) {
-    println!("{} {}", "Info:".cyan(), message.cyan());
+    eprintln!("{} {}", "Info:".cyan(), message.cyan());
+}
+
+// details of cache use, requests and timing, only shown with --verbose
+pub fn print_verbose(message: &str) {
+    if is_verbose() {
+        eprintln!("{} {}", "Verbose:".dimmed(), message.dimmed());
+    }
 }
@@ -1,14 +1,17 @@
 use currency_converter_cli::{
-    cli::{missing_config, parse_cli_arguments},
+    cli::{missing_config, parse_cli_arguments, parse_global_options},
     utils::config::health_check,
 };
 
 #[tokio::main]
 async fn main() {
+    let args: Vec<String> = std::env::args().collect();
+    parse_global_options(&args).apply();
+
     if health_check() {
-        let action = parse_cli_arguments(std::env::args().collect()).await;
+        let action = parse_cli_arguments(args.clone()).await;
 
-        action.execute().await;
+        action.execute(args.get(1..).unwrap_or_default()).await;
     } else {
         missing_config().await;
     }
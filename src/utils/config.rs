@@ -24,7 +24,7 @@ const ENV_VARS_WITH_DESCRIPTION: [(&str, &str, &str); 4] = [
     (
         "COMMAND_HISTORY_CACHE_TIME_IN_HOURS",
         "u32",
-        "Cache time to live in hours for command history, if you don't want to cache rates set it to 0",
+        "Hours each command history entry is kept, if you don't want to keep a history set it to 0",
     )
 ];
 
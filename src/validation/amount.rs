@@ -0,0 +1,248 @@
+use std::str::FromStr;
+
+use rust_decimal::Decimal;
+
+use crate::currency::Currency;
+
+pub const AMOUNT_FORMAT_ENV: &str = "AMOUNT_FORMAT";
+pub const ALLOW_NEGATIVE_AMOUNTS_ENV: &str = "ALLOW_NEGATIVE_AMOUNTS";
+
+pub const AMOUNT_SUFFIXES: [(&str, i64); 5] = [
+    ("k", 1_000),
+    ("m", 1_000_000),
+    ("mn", 1_000_000),
+    ("b", 1_000_000_000),
+    ("bn", 1_000_000_000),
+];
+
+// leaves room for the exchange rates and fees applied to the amount
+pub const MAX_AMOUNT: i64 = 1_000_000_000_000_000;
+
+const GROUP_SEPARATORS: [char; 5] = [' ', '\u{a0}', '\u{202f}', '\'', '_'];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum AmountFormat {
+    // a single comma followed by exactly three digits groups thousands, otherwise it is a decimal comma
+    #[default]
+    Auto,
+    // 1,000.50
+    DecimalPoint,
+    // 1.000,50
+    DecimalComma,
+}
+
+impl FromStr for AmountFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "auto" => Ok(AmountFormat::Auto),
+            "point" | "dot" | "en" => Ok(AmountFormat::DecimalPoint),
+            "comma" | "eu" => Ok(AmountFormat::DecimalComma),
+            _ => Err(format!(
+                "{} is not a valid amount format, use auto, point or comma",
+                s
+            )),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct AmountParser {
+    format: AmountFormat,
+    allow_negative: bool,
+    max_decimal_places: Option<(u32, String)>,
+}
+
+impl AmountParser {
+    pub fn from_env() -> AmountParser {
+        dotenv::dotenv().ok();
+
+        let format = match std::env::var(AMOUNT_FORMAT_ENV) {
+            Ok(val) => val.parse::<AmountFormat>().unwrap_or_default(),
+            Err(_) => AmountFormat::default(),
+        };
+        let allow_negative = match std::env::var(ALLOW_NEGATIVE_AMOUNTS_ENV) {
+            Ok(val) => val.parse::<bool>().unwrap_or(false),
+            Err(_) => false,
+        };
+
+        AmountParser {
+            format,
+            allow_negative,
+            max_decimal_places: None,
+        }
+    }
+
+    pub fn with_format(mut self, format: AmountFormat) -> AmountParser {
+        self.format = format;
+        self
+    }
+
+    pub fn allow_negative(mut self, allow_negative: bool) -> AmountParser {
+        self.allow_negative = allow_negative;
+        self
+    }
+
+    pub fn for_currency(mut self, currency: &Currency) -> AmountParser {
+        self.max_decimal_places = Some((currency.get_minor_units(), currency.get_code().clone()));
+        self
+    }
+
+    pub fn parse(&self, input: &str) -> Result<Decimal, String> {
+        let invalid = || format!("{} is not a valid number", input);
+
+        let trimmed = input.trim();
+        let (is_negative, unsigned) = match trimmed.strip_prefix(['-', '−']) {
+            Some(unsigned) => (true, unsigned),
+            None => (false, trimmed.strip_prefix('+').unwrap_or(trimmed)),
+        };
+
+        let (number, multiplier) = split_suffix(unsigned);
+        let number: String = number
+            .chars()
+            .filter(|char| !GROUP_SEPARATORS.contains(char))
+            .collect();
+
+        if number.is_empty()
+            || !number
+                .chars()
+                .all(|char| char.is_ascii_digit() || char == '.' || char == ',')
+        {
+            return Err(invalid());
+        }
+
+        let normalized = self.normalize_separators(&number).ok_or_else(invalid)?;
+        let amount = Decimal::from_str(&normalized).map_err(|_| invalid())?;
+        let amount = amount
+            .checked_mul(Decimal::from(multiplier))
+            .ok_or_else(invalid)?;
+        let amount = match is_negative {
+            true => -amount,
+            false => amount,
+        };
+
+        if amount.abs() > Decimal::from(MAX_AMOUNT) {
+            return Err(format!(
+                "{} is too large, amounts are limited to {}",
+                input, MAX_AMOUNT
+            ));
+        }
+
+        if amount.is_sign_negative() && !amount.is_zero() && !self.allow_negative {
+            return Err(format!("{} must not be negative", input));
+        }
+
+        if let Some((max_decimal_places, code)) = &self.max_decimal_places {
+            if amount.normalize().scale() > *max_decimal_places {
+                return Err(format!(
+                    "{} has more decimal places than {} allows ({})",
+                    input, code, max_decimal_places
+                ));
+            }
+        }
+
+        Ok(amount)
+    }
+
+    // returns the number with grouping removed and a '.' decimal separator
+    fn normalize_separators(&self, number: &str) -> Option<String> {
+        let decimal_separator = match self.format {
+            AmountFormat::DecimalPoint => '.',
+            AmountFormat::DecimalComma => ',',
+            AmountFormat::Auto => {
+                let last_dot = number.rfind('.');
+                let last_comma = number.rfind(',');
+
+                match (last_dot, last_comma) {
+                    (Some(dot), Some(comma)) if comma > dot => ',',
+                    (Some(_), _) => '.',
+                    (None, Some(comma)) => {
+                        let commas = number.matches(',').count();
+                        let digits_after = number.len() - comma - 1;
+
+                        match commas == 1 && digits_after != 3 {
+                            true => ',',
+                            false => '.',
+                        }
+                    }
+                    (None, None) => '.',
+                }
+            }
+        };
+        let group_separator = match decimal_separator {
+            '.' => ',',
+            _ => '.',
+        };
+
+        let (integer, fraction) = match number.split_once(decimal_separator) {
+            Some((integer, fraction)) => (integer, Some(fraction)),
+            None => (number, None),
+        };
+
+        if let Some(fraction) = fraction {
+            if fraction.is_empty() || !fraction.chars().all(|char| char.is_ascii_digit()) {
+                return None;
+            }
+        }
+
+        let groups: Vec<&str> = integer.split(group_separator).collect();
+        let valid_groups = match groups.as_slice() {
+            [single] => !single.is_empty() || fraction.is_some(),
+            [first, rest @ ..] => {
+                (1..=3).contains(&first.len()) && rest.iter().all(|group| group.len() == 3)
+            }
+            [] => false,
+        };
+
+        if !valid_groups {
+            return None;
+        }
+
+        let integer = match groups.concat() {
+            integer if integer.is_empty() => "0".to_string(),
+            integer => integer,
+        };
+
+        match fraction {
+            Some(fraction) => Some(format!("{}.{}", integer, fraction)),
+            None => Some(integer),
+        }
+    }
+}
+
+// suffixes are ASCII and compared without lowercasing the input, which can change its length
+pub fn split_suffix(input: &str) -> (&str, i64) {
+    match AMOUNT_SUFFIXES
+        .iter()
+        .filter(|(suffix, _)| ends_with_suffix(input, suffix))
+        .max_by_key(|(suffix, _)| suffix.len())
+    {
+        Some((suffix, multiplier)) => (input[..input.len() - suffix.len()].trim_end(), *multiplier),
+        None => (input, 1),
+    }
+}
+
+// the multiplier at the start of "kEUR" in "100kEUR", and the text after it
+pub fn split_leading_suffix(text: &str) -> Option<(i64, &str)> {
+    AMOUNT_SUFFIXES
+        .iter()
+        .filter(|(suffix, _)| {
+            text.get(..suffix.len())
+                .is_some_and(|start| start.eq_ignore_ascii_case(suffix))
+        })
+        .max_by_key(|(suffix, _)| suffix.len())
+        .map(|(suffix, multiplier)| (*multiplier, &text[suffix.len()..]))
+}
+
+fn ends_with_suffix(input: &str, suffix: &str) -> bool {
+    input
+        .len()
+        .checked_sub(suffix.len())
+        .and_then(|start| input.get(start..))
+        .is_some_and(|end| end.eq_ignore_ascii_case(suffix))
+}
+
+pub fn parse_amount(input: &str) -> Result<Decimal, String> {
+    AmountParser::from_env().parse(input)
+}
@@ -1,23 +1,31 @@
 use crate::currency::Currency;
 
+use self::{
+    amount::AmountParser,
+    suggestions::{suggest_currencies, with_suggestions},
+};
+
+pub mod amount;
+pub mod suggestions;
+
 pub enum ValidationType {
     Amount,
+    // the amount must not be more precise than the minor unit of the currency
+    AmountIn(Currency),
     Currency,
 }
 
 pub async fn validate(
-    val: &String,
+    val: &str,
     validation_type: ValidationType,
     supported_currencies: Option<&Vec<Currency>>,
 ) -> Result<(), String> {
     match validation_type {
-        ValidationType::Amount => {
-            if val.parse::<f64>().is_ok() {
-                Ok(())
-            } else {
-                Err(format!("{} is not a valid number", val))
-            }
-        }
+        ValidationType::Amount => AmountParser::from_env().parse(val).map(|_| ()),
+        ValidationType::AmountIn(currency) => AmountParser::from_env()
+            .for_currency(&currency)
+            .parse(val)
+            .map(|_| ()),
         ValidationType::Currency => {
             let supported_currencies = supported_currencies.unwrap();
             let currency = val.to_uppercase();
@@ -28,7 +36,10 @@ pub async fn validate(
             {
                 Ok(())
             } else {
-                Err(format!("{} is not a valid currency", currency))
+                Err(with_suggestions(
+                    format!("{} is not a valid currency", currency),
+                    &suggest_currencies(&currency, supported_currencies),
+                ))
             }
         }
     }
@@ -0,0 +1,66 @@
+use crate::currency::Currency;
+
+const MAX_SUGGESTIONS: usize = 3;
+const MAX_CODE_DISTANCE: usize = 1;
+
+pub fn edit_distance(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+
+    for (i, a_char) in a.iter().enumerate() {
+        let mut current = vec![i + 1];
+
+        for (j, b_char) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(a_char != b_char);
+            current.push(substitution.min(previous[j + 1] + 1).min(current[j] + 1));
+        }
+
+        previous = current;
+    }
+
+    previous[b.len()]
+}
+
+// codes a single edit away and names with a few typos, closest first
+pub fn suggest_currencies(input: &str, currencies: &[Currency]) -> Vec<String> {
+    let input = input.trim().to_lowercase();
+    let max_name_distance = input.chars().count() / 4;
+
+    let mut candidates: Vec<(usize, &String)> = currencies
+        .iter()
+        .filter_map(|currency| {
+            let code_distance = edit_distance(&input, &currency.get_code().to_lowercase());
+            let name_distance = edit_distance(&input, &currency.get_name().to_lowercase());
+
+            let distance = match (
+                code_distance <= MAX_CODE_DISTANCE,
+                name_distance <= max_name_distance,
+            ) {
+                (true, true) => code_distance.min(name_distance),
+                (true, false) => code_distance,
+                (false, true) => name_distance,
+                (false, false) => return None,
+            };
+
+            Some((distance, currency.get_code()))
+        })
+        .collect();
+
+    candidates.sort();
+    candidates.dedup_by(|a, b| a.1 == b.1);
+
+    candidates
+        .into_iter()
+        .take(MAX_SUGGESTIONS)
+        .map(|(_, code)| code.clone())
+        .collect()
+}
+
+pub fn with_suggestions(message: String, suggestions: &[String]) -> String {
+    match suggestions.is_empty() {
+        true => message,
+        false => format!("{}, did you mean {}?", message, suggestions.join(", ")),
+    }
+}
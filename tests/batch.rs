@@ -0,0 +1,208 @@
+#[cfg(test)]
+mod tests {
+    use std::{collections::HashMap, str::FromStr};
+
+    use chrono::{NaiveDate, TimeZone, Utc};
+    use currency_converter_cli::{
+        cli::{
+            batch::{
+                convert_batch_rows, read_batch_rows, validate_batch_rows, write_batch_csv,
+                BatchResult, RateTableKey,
+            },
+            options::{ConversionOptions, OutputFormat},
+            parse_cli_arguments, Action,
+        },
+        currency::{get_iso_currencies, Currency, RateTable},
+    };
+    use rust_decimal::Decimal;
+
+    fn rate_tables() -> HashMap<RateTableKey, Result<RateTable, String>> {
+        let usd_rates = HashMap::from([
+            ("EUR".to_string(), Decimal::from_str("0.9219").unwrap()),
+            ("JPY".to_string(), Decimal::from_str("151.37").unwrap()),
+        ]);
+        let usd = RateTable::new(Currency::new_from_code("USD".to_string()), usd_rates)
+            .with_updated_at(Some(Utc.with_ymd_and_hms(2024, 4, 2, 0, 0, 1).unwrap()));
+
+        let date = NaiveDate::from_ymd_opt(2024, 1, 15).unwrap();
+        let historical_rates =
+            HashMap::from([("EUR".to_string(), Decimal::from_str("0.9").unwrap())]);
+        let historical =
+            RateTable::new(Currency::new_from_code("USD".to_string()), historical_rates)
+                .with_updated_at(date.and_hms_opt(0, 0, 0).map(|date| date.and_utc()));
+
+        HashMap::from([
+            (("USD".to_string(), None), Ok(usd)),
+            (("USD".to_string(), Some(date)), Ok(historical)),
+            (
+                ("XYZ".to_string(), None),
+                Err("Unsupported currency code".to_string()),
+            ),
+        ])
+    }
+
+    #[tokio::test]
+    async fn test_read_batch_rows() {
+        let input =
+            "source,target,amount,date\nusd,eur,\"1,000.50\"\nUSD,JPY,10,2024-01-15\n\nUSD,EUR\n";
+
+        let rows = read_batch_rows(input.as_bytes());
+
+        assert_eq!(rows.len(), 3);
+        assert_eq!(rows[0].line, 2);
+        assert_eq!(rows[0].get_source(), "USD");
+        assert_eq!(rows[0].get_target(), "EUR");
+        assert_eq!(rows[0].get_amount(), "1,000.50");
+        assert_eq!(rows[0].get_date(), None);
+        assert_eq!(rows[1].get_date(), Some("2024-01-15"));
+        assert_eq!(
+            rows[1].get_rate_table_key(),
+            Ok(("USD".to_string(), NaiveDate::from_ymd_opt(2024, 1, 15)))
+        );
+        assert_eq!(
+            rows[2].get_rate_table_key(),
+            Err(
+                "Expected source, target, amount and an optional date, found 2 columns".to_string()
+            )
+        );
+    }
+
+    #[tokio::test]
+    async fn test_convert_batch_rows() {
+        let input = "USD,EUR,\"1,000.50\"\nUSD,EUR,100,2024-01-15\nUSD,JPY,100\n";
+        let rows = read_batch_rows(input.as_bytes());
+
+        let results = convert_batch_rows(&rows, &rate_tables(), &ConversionOptions::default());
+
+        assert_eq!(
+            results[0],
+            BatchResult {
+                line: 1,
+                source: "USD".to_string(),
+                target: "EUR".to_string(),
+                amount: "1,000.50".to_string(),
+                date: None,
+                rate: Some("0.9219".to_string()),
+                result: Some("922.36".to_string()),
+                rate_timestamp: Some("2024-04-02T00:00:01+00:00".to_string()),
+                error: None,
+            }
+        );
+        assert_eq!(results[1].result, Some("90.00".to_string()));
+        assert_eq!(
+            results[1].rate_timestamp,
+            Some("2024-01-15T00:00:00+00:00".to_string())
+        );
+        assert_eq!(results[2].result, Some("15137".to_string()));
+    }
+
+    #[tokio::test]
+    async fn test_convert_batch_rows_reports_errors_per_row() {
+        let input = "USD,EUR,abc\nXYZ,EUR,10\nUSD,GBP,10\nUSD,EUR,10,15/01/2024\nUSD,EUR,10.505\nUSD,EUR,10\n";
+        let rows = read_batch_rows(input.as_bytes());
+
+        let results = convert_batch_rows(&rows, &rate_tables(), &ConversionOptions::default());
+        let errors: Vec<Option<String>> = results.into_iter().map(|result| result.error).collect();
+
+        assert_eq!(
+            errors,
+            vec![
+                Some("abc is not a valid number".to_string()),
+                Some("Unsupported currency code".to_string()),
+                Some("Exchange rate for GBP not found".to_string()),
+                Some("15/01/2024 is not a valid date, use YYYY-MM-DD".to_string()),
+                Some("10.505 has more decimal places than USD allows (2)".to_string()),
+                None,
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_validate_batch_rows() {
+        let input = "USD,ABC,10\nUSD,EUR,10\nUSD,EUR\n";
+        let mut rows = read_batch_rows(input.as_bytes());
+
+        validate_batch_rows(&mut rows, get_iso_currencies()).await;
+
+        assert!(rows[0]
+            .error
+            .as_ref()
+            .is_some_and(|error| error.starts_with("ABC is not a valid currency")));
+        assert_eq!(rows[1].error, None);
+        assert_eq!(rows[2].error, None);
+
+        let results = convert_batch_rows(&rows, &rate_tables(), &ConversionOptions::default());
+
+        assert_eq!(results[0].error, rows[0].error);
+        assert_eq!(results[1].result, Some("9.22".to_string()));
+    }
+
+    #[tokio::test]
+    async fn test_write_batch_csv() {
+        let rows = read_batch_rows("USD,EUR,100\nUSD,GBP,10\n".as_bytes());
+        let results = convert_batch_rows(&rows, &rate_tables(), &ConversionOptions::default());
+
+        let mut output: Vec<u8> = vec![];
+        write_batch_csv(&results, &mut output).unwrap();
+
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            "source,target,amount,date,rate,result,rate_timestamp,error\n\
+             USD,EUR,100,,0.9219,92.19,2024-04-02T00:00:01+00:00,\n\
+             USD,GBP,10,,,,,Exchange rate for GBP not found\n"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_batch_parse_args() {
+        let args = vec![
+            "currency_converter_cli".to_string(),
+            "batch".to_string(),
+            "expenses.csv".to_string(),
+            "--format".to_string(),
+            "json".to_string(),
+        ];
+
+        let action = parse_cli_arguments(args).await;
+
+        assert_eq!(
+            action,
+            Action::Batch {
+                input: Some("expenses.csv".to_string()),
+                format: OutputFormat::Json,
+                options: ConversionOptions::default(),
+            }
+        );
+
+        let args = vec!["currency_converter_cli".to_string(), "batch".to_string()];
+
+        assert_eq!(
+            parse_cli_arguments(args).await,
+            Action::Batch {
+                input: None,
+                format: OutputFormat::Csv,
+                options: ConversionOptions::default(),
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_batch_invalid_format_parse_args() {
+        let args = vec![
+            "currency_converter_cli".to_string(),
+            "batch".to_string(),
+            "--format".to_string(),
+            "markdown".to_string(),
+        ];
+
+        let action = parse_cli_arguments(args).await;
+
+        assert_eq!(
+            action,
+            Action::Error {
+                message: "Output format markdown is not supported here, use one of: csv, json"
+                    .to_string()
+            }
+        );
+    }
+}
@@ -1,6 +1,16 @@
 #[cfg(test)]
 mod tests {
-    use currency_converter_cli::conversion::convert;
+    use std::str::FromStr;
+
+    use currency_converter_cli::{
+        conversion::{
+            convert, convert_and_round, convert_decimal,
+            converter::{Converter, FeeCurrency, FeeSchedule},
+            rounding::RoundingMode,
+        },
+        currency::Currency,
+    };
+    use rust_decimal::Decimal;
 
     #[tokio::test]
     async fn test_converter() {
@@ -11,4 +21,208 @@ mod tests {
 
         assert_eq!(conversion, 200.0);
     }
+
+    #[tokio::test]
+    async fn test_decimal_converter_is_exact() {
+        let amount = Decimal::from_str("0.1").unwrap();
+        let rate = Decimal::from_str("0.9219").unwrap();
+
+        let conversion = convert_decimal(amount, rate);
+
+        assert_eq!(conversion.to_string(), "0.09219");
+    }
+
+    #[tokio::test]
+    async fn test_f64_converter_uses_decimal_arithmetic() {
+        let conversion = convert(0.1, 0.9219);
+
+        assert_eq!(conversion, 0.09219);
+    }
+
+    #[tokio::test]
+    async fn test_rounding_modes_on_target_minor_unit() {
+        let usd = Currency::new_from_code("USD".to_string());
+        let amount = Decimal::from_str("2.5").unwrap();
+        let rate = Decimal::from_str("1.001").unwrap(); // 2.5025
+
+        let results = [
+            (RoundingMode::HalfUp, "2.50"),
+            (RoundingMode::HalfEven, "2.50"),
+            (RoundingMode::Floor, "2.50"),
+            (RoundingMode::Ceiling, "2.51"),
+            (RoundingMode::Truncate, "2.50"),
+        ];
+
+        for (rounding, expected) in results {
+            let conversion = convert_and_round(amount, rate, &usd, rounding);
+            assert_eq!(conversion.to_string(), expected, "{}", rounding);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_half_up_and_half_even_differ_on_midpoint() {
+        let jpy = Currency::new_from_code("JPY".to_string());
+        let amount = Decimal::from_str("2.5").unwrap();
+
+        assert_eq!(
+            jpy.round_amount_with(amount, RoundingMode::HalfUp),
+            Decimal::from(3)
+        );
+        assert_eq!(
+            jpy.round_amount_with(amount, RoundingMode::HalfEven),
+            Decimal::from(2)
+        );
+        assert_eq!(
+            jpy.round_amount_with(-amount, RoundingMode::Floor),
+            Decimal::from(-3)
+        );
+        assert_eq!(
+            jpy.round_amount_with(-amount, RoundingMode::Truncate),
+            Decimal::from(-2)
+        );
+    }
+
+    fn usd_to_eur_converter(fees: FeeSchedule) -> Converter {
+        Converter::new(
+            Currency::new_from_code("USD".to_string()),
+            Currency::new_from_code("EUR".to_string()),
+            Decimal::from_str("0.9").unwrap(),
+        )
+        .with_fees(fees)
+    }
+
+    #[tokio::test]
+    async fn test_converter_without_fees() {
+        let breakdown = usd_to_eur_converter(FeeSchedule::default())
+            .convert(Decimal::from(100))
+            .unwrap();
+
+        assert_eq!(breakdown.mid_market_result.to_string(), "90.00");
+        assert_eq!(breakdown.net_result.to_string(), "90.00");
+        assert!(breakdown.fees.is_zero());
+    }
+
+    #[tokio::test]
+    async fn test_converter_with_spread_and_source_fee() {
+        let fees = FeeSchedule {
+            spread_percent: Decimal::from(1),
+            fixed_fee: Decimal::from(10),
+            fixed_fee_currency: FeeCurrency::Source,
+            minimum_fee: Decimal::ZERO,
+        };
+
+        let breakdown = usd_to_eur_converter(fees)
+            .convert(Decimal::from(100))
+            .unwrap();
+
+        // (100 - 10) * 0.9 * 0.99
+        assert_eq!(
+            breakdown.effective_rate,
+            Decimal::from_str("0.891").unwrap()
+        );
+        assert_eq!(breakdown.net_result.to_string(), "80.19");
+        assert_eq!(breakdown.fees.to_string(), "9.81");
+    }
+
+    #[tokio::test]
+    async fn test_converter_with_target_fee_and_minimum_fee() {
+        let fees = FeeSchedule {
+            spread_percent: Decimal::ZERO,
+            fixed_fee: Decimal::from(1),
+            fixed_fee_currency: FeeCurrency::Target,
+            minimum_fee: Decimal::from(5),
+        };
+
+        let breakdown = usd_to_eur_converter(fees)
+            .convert(Decimal::from(100))
+            .unwrap();
+
+        assert_eq!(breakdown.net_result.to_string(), "85.00");
+        assert_eq!(breakdown.fees.to_string(), "5.00");
+    }
+
+    #[tokio::test]
+    async fn test_converter_fees_exceeding_amount() {
+        let fees = FeeSchedule {
+            fixed_fee: Decimal::from(20),
+            ..Default::default()
+        };
+
+        let breakdown = usd_to_eur_converter(fees).convert(Decimal::from(10));
+
+        assert_eq!(
+            breakdown,
+            Err("10 USD does not cover the conversion fees".to_string())
+        );
+    }
+
+    #[tokio::test]
+    async fn test_reverse_conversion_without_fees() {
+        let breakdown = usd_to_eur_converter(FeeSchedule::default())
+            .convert_reverse(Decimal::from(500))
+            .unwrap();
+
+        // 555.55 * 0.9 = 499.995, which rounds to 500.00
+        assert_eq!(breakdown.amount.to_string(), "555.55");
+        assert!(breakdown.net_result >= Decimal::from(500));
+    }
+
+    #[tokio::test]
+    async fn test_reverse_conversion_with_fees_round_trips() {
+        let fees = FeeSchedule {
+            spread_percent: Decimal::from_str("0.5").unwrap(),
+            fixed_fee: Decimal::from(2),
+            fixed_fee_currency: FeeCurrency::Source,
+            minimum_fee: Decimal::from(5),
+        };
+        let converter = usd_to_eur_converter(fees).with_rounding(RoundingMode::Floor);
+
+        let breakdown = converter.convert_reverse(Decimal::from(500)).unwrap();
+        let one_cent_less = converter
+            .convert(breakdown.amount - Decimal::from_str("0.01").unwrap())
+            .unwrap();
+
+        assert!(breakdown.net_result >= Decimal::from(500));
+        assert!(one_cent_less.net_result < Decimal::from(500));
+    }
+
+    #[tokio::test]
+    async fn test_reverse_conversion_with_minimum_fee() {
+        let fees = FeeSchedule {
+            minimum_fee: Decimal::from(9),
+            ..Default::default()
+        };
+
+        let breakdown = usd_to_eur_converter(fees)
+            .convert_reverse(Decimal::from(90))
+            .unwrap();
+
+        assert_eq!(breakdown.amount.to_string(), "110.00");
+        assert_eq!(breakdown.net_result.to_string(), "90.00");
+        assert_eq!(breakdown.fees.to_string(), "9.00");
+    }
+
+    #[tokio::test]
+    async fn test_converter_out_of_range() {
+        let converter = Converter::new(
+            Currency::new_from_code("USD".to_string()),
+            Currency::new_from_code("JPY".to_string()),
+            Decimal::from(150),
+        );
+
+        assert_eq!(
+            converter
+                .convert(Decimal::MAX)
+                .map(|breakdown| breakdown.net_result),
+            Err("79228162514264337593543950335 USD is too large to convert".to_string())
+        );
+
+        let converter = Converter::new(
+            Currency::new_from_code("JPY".to_string()),
+            Currency::new_from_code("USD".to_string()),
+            Decimal::from_str("0.0066").unwrap(),
+        );
+
+        assert!(converter.convert_reverse(Decimal::MAX).is_err());
+    }
 }
@@ -0,0 +1,116 @@
+#[cfg(test)]
+mod tests {
+    use std::{collections::HashMap, str::FromStr};
+
+    use currency_converter_cli::currency::{
+        get_iso_currencies, get_validation_currencies, Currency, RateTable,
+    };
+    use rust_decimal::Decimal;
+
+    #[tokio::test]
+    async fn test_currency_minor_units() {
+        let jpy = Currency::new_from_code("jpy".to_string());
+        let kwd = Currency::new_from_code("KWD".to_string());
+        let usd = Currency::new_from_code("USD".to_string());
+
+        assert_eq!(jpy.get_minor_units(), 0);
+        assert_eq!(kwd.get_minor_units(), 3);
+        assert_eq!(usd.get_minor_units(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_currency_metadata() {
+        let eur = Currency::new_from_code("EUR".to_string());
+
+        assert_eq!(eur.get_numeric_code(), Some(978));
+        assert_eq!(eur.get_symbol(), "€");
+        assert_eq!(eur.get_narrow_symbol(), "€");
+        assert_eq!(eur.get_name(), "Euro");
+    }
+
+    #[tokio::test]
+    async fn test_unknown_currency_metadata_fallback() {
+        let unknown = Currency::new_from_code("XYZ".to_string());
+
+        assert_eq!(unknown.get_numeric_code(), None);
+        assert_eq!(unknown.get_minor_units(), 2);
+        assert_eq!(unknown.get_symbol(), "XYZ");
+    }
+
+    #[tokio::test]
+    async fn test_format_amount_uses_minor_units() {
+        let amount = Decimal::from_str("1234.5678").unwrap();
+
+        let jpy = Currency::new_from_code("JPY".to_string());
+        let kwd = Currency::new_from_code("KWD".to_string());
+        let usd = Currency::new_from_code("USD".to_string());
+
+        assert_eq!(jpy.format_amount(amount), "1235");
+        assert_eq!(kwd.format_amount(amount), "1234.568");
+        assert_eq!(usd.format_amount(amount), "1234.57");
+        assert_eq!(usd.format_amount(Decimal::from(5)), "5.00");
+    }
+
+    #[tokio::test]
+    async fn test_format_rate_keeps_precision() {
+        let usd = Currency::new_from_code("USD".to_string());
+
+        assert_eq!(
+            usd.format_rate(Decimal::from_str("0.921900").unwrap()),
+            "0.9219"
+        );
+        assert_eq!(usd.format_rate(Decimal::from(1)), "1.00");
+    }
+
+    #[tokio::test]
+    async fn test_rate_table_cross_rate() {
+        let rates = HashMap::from([
+            ("EUR".to_string(), Decimal::from_str("0.8").unwrap()),
+            ("GBP".to_string(), Decimal::from_str("0.64").unwrap()),
+        ]);
+        let rate_table = RateTable::new(Currency::new_from_code("USD".to_string()), rates);
+
+        assert_eq!(
+            rate_table.get_cross_rate("EUR", "GBP"),
+            Ok(Decimal::from_str("0.8").unwrap())
+        );
+        assert_eq!(
+            rate_table.get_cross_rate("eur", "USD"),
+            Ok(Decimal::from_str("1.25").unwrap())
+        );
+        assert_eq!(
+            rate_table.get_cross_rate("USD", "GBP"),
+            Ok(Decimal::from_str("0.64").unwrap())
+        );
+        assert_eq!(
+            rate_table.get_cross_rate("CHF", "GBP"),
+            Err("Exchange rate for CHF not found".to_string())
+        );
+    }
+
+    #[tokio::test]
+    async fn test_embedded_currency_list() {
+        let currencies = get_iso_currencies();
+        let eur = currencies
+            .iter()
+            .find(|currency| currency.get_code() == "EUR")
+            .unwrap();
+
+        assert_eq!(eur.get_name(), "Euro");
+        assert!(currencies
+            .iter()
+            .any(|currency| currency.get_code() == "FOK"));
+    }
+
+    #[tokio::test]
+    async fn test_validation_currencies_without_request() {
+        let codes = vec!["usd".to_string(), "JPY".to_string()];
+        let currencies = get_validation_currencies(&codes).await;
+
+        for code in ["USD", "JPY"] {
+            assert!(currencies
+                .iter()
+                .any(|currency| currency.get_code() == code));
+        }
+    }
+}
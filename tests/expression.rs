@@ -0,0 +1,96 @@
+#[cfg(test)]
+mod tests {
+    use std::{collections::HashMap, str::FromStr};
+
+    use currency_converter_cli::{
+        cli::{parse_cli_arguments, Action},
+        conversion::expression::{is_conversion_expression, ConversionExpression},
+    };
+    use rust_decimal::Decimal;
+
+    fn rates_to_pln() -> HashMap<String, Decimal> {
+        HashMap::from([
+            ("USD".to_string(), Decimal::from(4)),
+            ("EUR".to_string(), Decimal::from_str("4.5").unwrap()),
+            ("GBP".to_string(), Decimal::from(5)),
+        ])
+    }
+
+    #[tokio::test]
+    async fn test_mixed_currency_expression() {
+        let expression = ConversionExpression::parse("100 USD + 250 EUR - 30 GBP in PLN").unwrap();
+
+        assert_eq!(expression.get_target().get_code(), "PLN");
+        assert_eq!(expression.get_currencies(), vec!["USD", "EUR", "GBP"]);
+        assert_eq!(
+            expression.evaluate(&rates_to_pln()),
+            Ok(Decimal::from_str("1375.0").unwrap())
+        );
+    }
+
+    #[tokio::test]
+    async fn test_expression_precedence_and_parentheses() {
+        let expression =
+            ConversionExpression::parse("(1200 usd / 12) * 2 + 10 pln to pln").unwrap();
+
+        assert_eq!(expression.evaluate(&rates_to_pln()), Ok(Decimal::from(810)));
+    }
+
+    #[tokio::test]
+    async fn test_expression_type_errors() {
+        let rates = rates_to_pln();
+
+        let money_times_money = ConversionExpression::parse("10 USD * 2 EUR in PLN").unwrap();
+        let money_plus_number = ConversionExpression::parse("10 USD + 2 in PLN").unwrap();
+        let division_by_zero = ConversionExpression::parse("10 USD / 0 in PLN").unwrap();
+
+        assert_eq!(
+            money_times_money.evaluate(&rates),
+            Err("Cannot multiply two amounts of money".to_string())
+        );
+        assert_eq!(
+            money_plus_number.evaluate(&rates),
+            Err("Cannot add or subtract a plain number and an amount of money".to_string())
+        );
+        assert_eq!(
+            division_by_zero.evaluate(&rates),
+            Err("Division by zero".to_string())
+        );
+    }
+
+    #[tokio::test]
+    async fn test_expression_parse_errors() {
+        assert_eq!(
+            ConversionExpression::parse("100 USD + 250 EUR"),
+            Err("Missing target currency, end the expression with 'in <currency>'".to_string())
+        );
+        assert_eq!(
+            ConversionExpression::parse("(100 USD in PLN"),
+            Err("Missing closing parenthesis".to_string())
+        );
+        assert_eq!(
+            ConversionExpression::parse("100 USD in"),
+            Err("Expected a currency code after 'in'".to_string())
+        );
+    }
+
+    #[tokio::test]
+    async fn test_expression_parse_args() {
+        assert!(is_conversion_expression("100 USD + 250 EUR in PLN"));
+        assert!(!is_conversion_expression("history"));
+
+        let expression_args = vec![
+            "currency_converter_cli".to_string(),
+            "100 USD + 250 EUR - 30 GBP in PLN".to_string(),
+        ];
+
+        let action = parse_cli_arguments(expression_args).await;
+
+        assert_eq!(
+            action,
+            Action::Expression {
+                expression: "100 USD + 250 EUR - 30 GBP in PLN".to_string()
+            }
+        );
+    }
+}
@@ -0,0 +1,691 @@
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use std::collections::HashMap;
+
+    use chrono::{DateTime, NaiveDate};
+    use currency_converter_cli::{
+        cli::{
+            history::{write_history_export, HistoryCommand},
+            history_stats::{get_history_stats, PeriodCount},
+            options::OutputFormat,
+            parse_cli_arguments, Action,
+        },
+        conversion::{converter::FeeSchedule, rounding::RoundingMode},
+        currency::{
+            history::{
+                assign_ids, CommandHistory, ConversionRecord, HistoryFilter, HistoryOutcome,
+                HistoryRecording, HistoryRetention, StoredCommandHistory,
+            },
+            Currency, RateTable,
+        },
+    };
+    use rust_decimal::Decimal;
+
+    fn decimal(value: &str) -> Decimal {
+        Decimal::from_str(value).unwrap()
+    }
+
+    fn load(json: &str) -> Vec<CommandHistory> {
+        serde_json::from_str::<Vec<StoredCommandHistory>>(json)
+            .unwrap()
+            .into_iter()
+            .map(CommandHistory::from)
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_history_renders_structured_conversions() {
+        let history =
+            CommandHistory::new("USD EUR,JPY 100".to_string(), None).with_conversions(vec![
+                ConversionRecord {
+                    source: "USD".to_string(),
+                    target: "EUR".to_string(),
+                    amount: decimal("100"),
+                    rate: decimal("0.92"),
+                    result: decimal("92"),
+                    reverse: false,
+                    fees: FeeSchedule::default(),
+                },
+            ]);
+
+        assert_eq!(
+            history.get_output(),
+            "100 USD = 92.00 EUR  // exchange rate: 0.92"
+        );
+
+        let json = serde_json::to_string(&vec![history.clone()]).unwrap();
+        assert!(!json.contains('\u{1b}'));
+        assert_eq!(load(&json), vec![history]);
+    }
+
+    #[tokio::test]
+    async fn test_history_migrates_legacy_entries() {
+        let json = r#"[
+            {
+                "command": "USD EUR 100",
+                "output": "\u001b[1m100\u001b[0m USD = \u001b[32m92.00\u001b[0m EUR  // exchange rate: \u001b[33m0.92\u001b[0m",
+                "timestamp": "Mon, 1 Apr 2024 10:00:00 +0000",
+                "rounding": "half-even"
+            },
+            {
+                "command": "batch expenses.csv",
+                "output": "Converted 3 of 3 rows, 0 failed",
+                "timestamp": "Mon, 1 Apr 2024 11:00:00 +0000"
+            }
+        ]"#;
+
+        let history = load(json);
+
+        assert_eq!(
+            history[0].get_conversions(),
+            &vec![ConversionRecord {
+                source: "USD".to_string(),
+                target: "EUR".to_string(),
+                amount: decimal("100"),
+                rate: decimal("0.92"),
+                result: decimal("92.00"),
+                reverse: false,
+                fees: FeeSchedule::default(),
+            }]
+        );
+        assert_eq!(history[0].get_rounding(), Some(RoundingMode::HalfEven));
+        assert_eq!(history[0].get_timestamp(), "Mon, 1 Apr 2024 10:00:00 +0000");
+
+        assert!(history[1].get_conversions().is_empty());
+        assert_eq!(history[1].get_output(), "Converted 3 of 3 rows, 0 failed");
+    }
+
+    fn sample_history() -> Vec<CommandHistory> {
+        load(
+            r#"[
+            {
+                "command": "USD EUR 100",
+                "timestamp": "Mon, 1 Apr 2024 10:00:00 +0000",
+                "conversions": [
+                    {"source": "USD", "target": "EUR", "amount": "100", "rate": "0.92", "result": "92"}
+                ]
+            },
+            {
+                "command": "GBP EUR,JPY 2500",
+                "timestamp": "Wed, 3 Apr 2024 10:00:00 +0000",
+                "conversions": [
+                    {"source": "GBP", "target": "EUR", "amount": "2500", "rate": "1.17", "result": "2925"},
+                    {"source": "GBP", "target": "JPY", "amount": "2500", "rate": "190", "result": "475000"}
+                ]
+            },
+            {
+                "command": "batch expenses.csv",
+                "timestamp": "2024-04-05T10:00:00+00:00",
+                "conversions": [],
+                "summary": "Converted 3 of 3 rows, 0 failed"
+            },
+            {
+                "command": "EUR USD 40",
+                "timestamp": "Sat, 6 Apr 2024 10:00:00 +0000",
+                "conversions": [
+                    {"source": "EUR", "target": "USD", "amount": "40", "rate": "1.08", "result": "43.2"}
+                ]
+            }
+        ]"#,
+        )
+    }
+
+    fn commands(history: Vec<CommandHistory>) -> Vec<String> {
+        history
+            .into_iter()
+            .map(|entry| entry.get_command().clone())
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_history_filter_by_currencies_and_amounts() {
+        let filter = HistoryFilter {
+            target: Some("EUR".to_string()),
+            ..HistoryFilter::default()
+        };
+        assert_eq!(
+            commands(filter.apply(sample_history())),
+            vec!["USD EUR 100", "GBP EUR,JPY 2500"]
+        );
+
+        let filter = HistoryFilter::default().with_pair("gbp/jpy").unwrap();
+        assert_eq!(
+            commands(filter.apply(sample_history())),
+            vec!["GBP EUR,JPY 2500"]
+        );
+
+        // the pair must match within a single conversion
+        let filter = HistoryFilter::default().with_pair("USDJPY").unwrap();
+        assert!(filter.apply(sample_history()).is_empty());
+        assert_eq!(
+            HistoryFilter::default().with_pair("aa€a"),
+            Err("aa€a is not a valid currency pair, example: USD/EUR".to_string())
+        );
+
+        let filter = HistoryFilter {
+            min_amount: Some(decimal("50")),
+            max_amount: Some(decimal("1000")),
+            ..HistoryFilter::default()
+        };
+        assert_eq!(
+            commands(filter.apply(sample_history())),
+            vec!["USD EUR 100"]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_history_filter_by_date_text_and_last() {
+        let filter = HistoryFilter {
+            since: NaiveDate::from_ymd_opt(2024, 4, 3),
+            until: NaiveDate::from_ymd_opt(2024, 4, 5),
+            ..HistoryFilter::default()
+        };
+        assert_eq!(
+            commands(filter.apply(sample_history())),
+            vec!["GBP EUR,JPY 2500", "batch expenses.csv"]
+        );
+
+        let filter = HistoryFilter {
+            text: Some("ROWS".to_string()),
+            ..HistoryFilter::default()
+        };
+        assert_eq!(
+            commands(filter.apply(sample_history())),
+            vec!["batch expenses.csv"]
+        );
+
+        let filter = HistoryFilter {
+            last: Some(2),
+            ..HistoryFilter::default()
+        };
+        assert_eq!(
+            commands(filter.apply(sample_history())),
+            vec!["batch expenses.csv", "EUR USD 40"]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_history_filter_parse_args() {
+        let args = vec![
+            "currency_converter_cli",
+            "history",
+            "--pair",
+            "usd-eur",
+            "--since",
+            "2024-04-01",
+            "--last",
+            "5",
+        ]
+        .into_iter()
+        .map(String::from)
+        .collect();
+
+        let action = parse_cli_arguments(args).await;
+
+        assert_eq!(
+            action,
+            Action::HistoryCommand {
+                command: HistoryCommand::List(HistoryFilter {
+                    source: Some("USD".to_string()),
+                    target: Some("EUR".to_string()),
+                    since: NaiveDate::from_ymd_opt(2024, 4, 1),
+                    last: Some(5),
+                    ..HistoryFilter::default()
+                })
+            }
+        );
+
+        let args = vec!["currency_converter_cli", "history", "--pair", "USD"]
+            .into_iter()
+            .map(String::from)
+            .collect();
+
+        assert_eq!(
+            parse_cli_arguments(args).await,
+            Action::Error {
+                message: "USD is not a valid currency pair, example: USD/EUR".to_string()
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_replay_conversion_at_current_rates() {
+        let rate_table = RateTable::new(
+            Currency::new_from_code("USD".to_string()),
+            HashMap::from([
+                ("USD".to_string(), decimal("1")),
+                ("EUR".to_string(), decimal("0.95")),
+            ]),
+        );
+
+        let conversion = ConversionRecord {
+            source: "USD".to_string(),
+            target: "EUR".to_string(),
+            amount: decimal("100"),
+            rate: decimal("0.92"),
+            result: decimal("92"),
+            reverse: false,
+            fees: FeeSchedule::default(),
+        };
+        let replayed = conversion
+            .replay(&rate_table, RoundingMode::HalfEven)
+            .unwrap();
+
+        assert_eq!(replayed.current_rate, decimal("0.95"));
+        assert_eq!(replayed.original, decimal("92"));
+        assert_eq!(replayed.current, decimal("95"));
+        assert_eq!(replayed.difference, decimal("3"));
+        assert_eq!(
+            replayed.change_percent.unwrap().round_dp(2),
+            decimal("3.26")
+        );
+
+        // the source amount needed to receive the same result is compared
+        let conversion = ConversionRecord {
+            amount: decimal("100"),
+            result: decimal("95"),
+            reverse: true,
+            ..conversion
+        };
+        let replayed = conversion
+            .replay(&rate_table, RoundingMode::HalfEven)
+            .unwrap();
+
+        assert_eq!(conversion.get_compared_currency(), "USD");
+        assert_eq!(replayed.current, decimal("100"));
+        assert_eq!(replayed.difference, decimal("0"));
+
+        // the stored result is after fees, the same fees are applied at today's rates
+        let conversion = ConversionRecord {
+            amount: decimal("100"),
+            rate: decimal("0.95"),
+            result: decimal("93.10"),
+            reverse: false,
+            fees: FeeSchedule {
+                spread_percent: decimal("2"),
+                ..FeeSchedule::default()
+            },
+            ..conversion
+        };
+        let replayed = conversion
+            .replay(&rate_table, RoundingMode::HalfEven)
+            .unwrap();
+
+        assert_eq!(replayed.current, decimal("93.10"));
+        assert_eq!(replayed.difference, decimal("0"));
+    }
+
+    #[tokio::test]
+    async fn test_history_replay_parse_args() {
+        let parse = |args: Vec<&str>| {
+            let mut cli_args = vec!["currency_converter_cli".to_string(), "history".to_string()];
+            cli_args.extend(args.into_iter().map(String::from));
+            parse_cli_arguments(cli_args)
+        };
+
+        assert_eq!(
+            parse(vec!["replay", "#4"]).await,
+            Action::HistoryCommand {
+                command: HistoryCommand::Replay {
+                    id: Some(4),
+                    filter: HistoryFilter::default()
+                }
+            }
+        );
+        assert_eq!(
+            parse(vec!["replay", "--source", "usd"]).await,
+            Action::HistoryCommand {
+                command: HistoryCommand::Replay {
+                    id: None,
+                    filter: HistoryFilter {
+                        source: Some("USD".to_string()),
+                        ..HistoryFilter::default()
+                    }
+                }
+            }
+        );
+        assert_eq!(
+            parse(vec!["replay", "zero"]).await,
+            Action::Error {
+                message: "zero is not a valid history entry".to_string()
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_history_export_csv_and_markdown() {
+        let rows: Vec<_> = sample_history()[1..3]
+            .iter()
+            .flat_map(|entry| entry.get_export_rows())
+            .collect();
+
+        let mut csv = vec![];
+        write_history_export(&rows, OutputFormat::Csv, &mut csv).unwrap();
+
+        assert_eq!(
+            String::from_utf8(csv).unwrap(),
+            "id,timestamp,command,source,target,amount,rate,result,reverse,rounding,rate_timestamp,provider,summary\n\
+            0,2024-04-03T10:00:00+00:00,\"GBP EUR,JPY 2500\",GBP,EUR,2500,1.17,2925,false,,,,\n\
+            0,2024-04-03T10:00:00+00:00,\"GBP EUR,JPY 2500\",GBP,JPY,2500,190,475000,false,,,,\n\
+            0,2024-04-05T10:00:00+00:00,batch expenses.csv,,,,,,,,,,\"Converted 3 of 3 rows, 0 failed\"\n"
+        );
+
+        let mut markdown = vec![];
+        write_history_export(&rows[2..], OutputFormat::Markdown, &mut markdown).unwrap();
+        let markdown = String::from_utf8(markdown).unwrap();
+        let lines: Vec<&str> = markdown.lines().collect();
+
+        assert_eq!(lines.len(), 3);
+        assert_eq!(
+            lines[2],
+            "| 0 | 2024-04-05T10:00:00+00:00 | batch expenses.csv |  |  |  |  |  |  |  |  |  | Converted 3 of 3 rows, 0 failed |"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_history_export_parse_args() {
+        let args = vec![
+            "currency_converter_cli",
+            "history",
+            "export",
+            "--format",
+            "md",
+            "--output",
+            "history.md",
+            "--target",
+            "eur",
+        ]
+        .into_iter()
+        .map(String::from)
+        .collect();
+
+        assert_eq!(
+            parse_cli_arguments(args).await,
+            Action::HistoryCommand {
+                command: HistoryCommand::Export {
+                    format: OutputFormat::Markdown,
+                    output: Some("history.md".to_string()),
+                    filter: HistoryFilter {
+                        target: Some("EUR".to_string()),
+                        ..HistoryFilter::default()
+                    }
+                }
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_history_retention_per_entry() {
+        let now = DateTime::parse_from_rfc3339("2024-04-06T12:00:00+00:00")
+            .unwrap()
+            .to_utc();
+
+        let retention = HistoryRetention {
+            max_age_in_hours: 24 * 3,
+            max_entries: 1000,
+        };
+        assert_eq!(
+            commands(retention.apply(sample_history(), now)),
+            vec!["batch expenses.csv", "EUR USD 40"]
+        );
+
+        let retention = HistoryRetention {
+            max_age_in_hours: 24 * 365,
+            max_entries: 3,
+        };
+        assert_eq!(
+            commands(retention.apply(sample_history(), now)),
+            vec!["GBP EUR,JPY 2500", "batch expenses.csv", "EUR USD 40"]
+        );
+
+        // the log is also pruned while saving, every tenth of the maximum entries
+        let retention = HistoryRetention {
+            max_age_in_hours: 24 * 365,
+            max_entries: 1000,
+        };
+        assert!(!retention.is_prune_due(1099));
+        assert!(retention.is_prune_due(1100));
+    }
+
+    #[tokio::test]
+    async fn test_history_retention_keeps_pinned_entries() {
+        let now = DateTime::parse_from_rfc3339("2024-04-06T12:00:00+00:00")
+            .unwrap()
+            .to_utc();
+
+        let mut history = sample_history();
+        history[0] = load(
+            r#"[{
+                "id": 1,
+                "pinned": true,
+                "command": "USD EUR 100",
+                "timestamp": "Mon, 1 Apr 2024 10:00:00 +0000",
+                "conversions": []
+            }]"#,
+        )
+        .remove(0);
+        assert!(history[0].is_pinned());
+
+        let retention = HistoryRetention {
+            max_age_in_hours: 24 * 3,
+            max_entries: 1,
+        };
+        assert_eq!(
+            commands(retention.apply(history, now)),
+            vec!["USD EUR 100", "EUR USD 40"]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_history_assigns_stable_ids() {
+        let mut history = sample_history();
+        history.remove(1);
+
+        let (history, last_id) = assign_ids(history, 0);
+        let ids: Vec<usize> = history.iter().map(|entry| entry.get_id()).collect();
+        assert_eq!(ids, vec![1, 2, 3]);
+        assert_eq!(last_id, 3);
+
+        // ids of deleted entries are not given again
+        let mut history = history;
+        history.remove(2);
+        history.extend(sample_history().into_iter().take(1));
+
+        let (history, last_id) = assign_ids(history, 3);
+        let ids: Vec<usize> = history.iter().map(|entry| entry.get_id()).collect();
+        assert_eq!(ids, vec![1, 2, 4]);
+        assert_eq!(last_id, 4);
+    }
+
+    #[tokio::test]
+    async fn test_history_management_parse_args() {
+        let parse = |args: Vec<&str>| {
+            let mut cli_args = vec!["currency_converter_cli".to_string(), "history".to_string()];
+            cli_args.extend(args.into_iter().map(String::from));
+            parse_cli_arguments(cli_args)
+        };
+
+        assert_eq!(
+            parse(vec!["delete", "#12"]).await,
+            Action::HistoryCommand {
+                command: HistoryCommand::Delete { id: 12 }
+            }
+        );
+        assert_eq!(
+            parse(vec!["clear", "--before", "2024-01-01"]).await,
+            Action::HistoryCommand {
+                command: HistoryCommand::Clear {
+                    before: NaiveDate::from_ymd_opt(2024, 1, 1)
+                }
+            }
+        );
+        assert_eq!(
+            parse(vec!["unpin", "7"]).await,
+            Action::HistoryCommand {
+                command: HistoryCommand::Pin {
+                    id: 7,
+                    pinned: false
+                }
+            }
+        );
+        assert_eq!(
+            parse(vec!["delete"]).await,
+            Action::Error {
+                message: "Use history delete <id>".to_string()
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_history_stats() {
+        let mut history = sample_history();
+        history.extend(load(
+            r#"[{
+                "command": "USD EUR 300 --spread 2",
+                "timestamp": "2024-04-08T10:00:00+00:00",
+                "conversions": [
+                    {"source": "USD", "target": "EUR", "amount": "300", "rate": "0.94", "result": "276.36"}
+                ]
+            }]"#,
+        ));
+
+        let stats = get_history_stats(&history);
+
+        assert_eq!(stats.entries, 5);
+        assert_eq!(stats.conversions, 5);
+
+        let pairs: Vec<(String, usize)> = stats
+            .pairs
+            .iter()
+            .map(|pair| (format!("{}/{}", pair.source, pair.target), pair.conversions))
+            .collect();
+        assert_eq!(
+            pairs,
+            vec![
+                ("USD/EUR".to_string(), 2),
+                ("GBP/EUR".to_string(), 1),
+                ("GBP/JPY".to_string(), 1),
+                ("EUR/USD".to_string(), 1)
+            ]
+        );
+        assert_eq!(stats.pairs[0].volume, decimal("400"));
+        // the rate obtained after the spread, (92 + 276.36) / 400
+        assert_eq!(stats.pairs[0].average_rate, decimal("0.9209"));
+
+        let eur = stats
+            .volumes
+            .iter()
+            .find(|volume| volume.currency == "EUR")
+            .unwrap();
+        assert_eq!(eur.converted, decimal("40"));
+        assert_eq!(eur.received, decimal("3293.36"));
+
+        assert_eq!(stats.per_day.len(), 4);
+        assert_eq!(
+            stats.per_week,
+            vec![
+                PeriodCount {
+                    period: "2024-W14".to_string(),
+                    conversions: 4
+                },
+                PeriodCount {
+                    period: "2024-W15".to_string(),
+                    conversions: 1
+                }
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_history_stats_parse_args() {
+        let args = vec![
+            "currency_converter_cli",
+            "history",
+            "stats",
+            "--format",
+            "json",
+            "--since",
+            "2024-04-01",
+        ]
+        .into_iter()
+        .map(String::from)
+        .collect();
+
+        assert_eq!(
+            parse_cli_arguments(args).await,
+            Action::HistoryCommand {
+                command: HistoryCommand::Stats {
+                    format: OutputFormat::Json,
+                    filter: HistoryFilter {
+                        since: NaiveDate::from_ymd_opt(2024, 4, 1),
+                        ..HistoryFilter::default()
+                    }
+                }
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_history_records_outcome() {
+        let history = sample_history();
+        assert_eq!(history[0].get_outcome(), HistoryOutcome::Success);
+        assert_eq!(history[0].get_action(), None);
+
+        let arguments = vec!["USD".to_string(), "XXX".to_string(), "10".to_string()];
+        let entry = CommandHistory::new(arguments.join(" "), None)
+            .with_outcome(
+                HistoryOutcome::ValidationError,
+                Some("XXX is not a valid currency".to_string()),
+            )
+            .with_action("conversion", &arguments)
+            .with_duration(std::time::Duration::from_millis(42));
+
+        let stored = load(&format!("[{}]", serde_json::to_string(&entry).unwrap()));
+        assert_eq!(stored[0], entry);
+        assert_eq!(stored[0].get_action(), Some(&"conversion".to_string()));
+        assert_eq!(stored[0].get_arguments(), &arguments);
+        assert_eq!(stored[0].get_duration_in_ms(), Some(42));
+        assert_eq!(stored[0].get_output(), "XXX is not a valid currency");
+    }
+
+    #[tokio::test]
+    async fn test_history_recording_per_command_type() {
+        let recording = HistoryRecording::default();
+        assert!(recording.records("conversion"));
+        assert!(recording.records("invalid"));
+        assert!(!recording.records("history"));
+
+        let recording = HistoryRecording::parse("conversion, batch");
+        assert!(recording.records("batch"));
+        assert!(!recording.records("matrix"));
+
+        let recording = HistoryRecording::parse("-search,-list-currencies");
+        assert!(recording.records("history"));
+        assert!(!recording.records("search"));
+
+        assert_eq!(HistoryRecording::parse("all"), HistoryRecording::All);
+        assert!(!HistoryRecording::parse("none").records("conversion"));
+    }
+
+    #[tokio::test]
+    async fn test_action_names() {
+        let parse = |args: Vec<&str>| {
+            let mut cli_args = vec!["currency_converter_cli".to_string()];
+            cli_args.extend(args.into_iter().map(String::from));
+            parse_cli_arguments(cli_args)
+        };
+
+        assert_eq!(
+            parse(vec!["USD", "EUR", "100"]).await.get_name(),
+            "conversion"
+        );
+        assert_eq!(parse(vec!["history", "stats"]).await.get_name(), "history");
+        assert_eq!(
+            parse(vec!["-lr"]).await.get_name(),
+            "list-currencies-with-rates"
+        );
+        assert_eq!(parse(vec!["foo", "bar"]).await.get_name(), "invalid");
+    }
+}
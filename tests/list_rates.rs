@@ -0,0 +1,178 @@
+#[cfg(test)]
+mod tests {
+    use std::{collections::HashMap, str::FromStr};
+
+    use currency_converter_cli::{
+        cli::{
+            list_currencies_with_rates::{RateListOptions, RateRow, RateSort},
+            parse_cli_arguments, Action,
+        },
+        currency::{Currency, RateTable},
+    };
+    use rust_decimal::Decimal;
+
+    fn rate_table(base: &str, rates: &[(&str, &str)]) -> RateTable {
+        let rates = rates
+            .iter()
+            .map(|(code, rate)| (code.to_string(), Decimal::from_str(rate).unwrap()))
+            .collect::<HashMap<String, Decimal>>();
+
+        RateTable::new(Currency::new_from_code(base.to_string()), rates)
+    }
+
+    fn rate_tables() -> Vec<RateTable> {
+        vec![
+            rate_table(
+                "USD",
+                &[
+                    ("USD", "1"),
+                    ("JPY", "151.2"),
+                    ("EUR", "0.92"),
+                    ("GBP", "0.79"),
+                ],
+            ),
+            rate_table("EUR", &[("EUR", "1"), ("USD", "1.08"), ("GBP", "0.86")]),
+        ]
+    }
+
+    fn codes(rows: &[RateRow]) -> Vec<String> {
+        rows.iter()
+            .map(|row| format!("{}/{}", row.base, row.target))
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_select_rates_default_order() {
+        let page = RateListOptions::default().select_rates(&rate_tables());
+
+        assert_eq!(
+            codes(&page.rows),
+            vec!["EUR/EUR", "EUR/GBP", "EUR/USD", "USD/EUR", "USD/GBP", "USD/JPY", "USD/USD"]
+        );
+        assert_eq!((page.page, page.pages), (1, 1));
+    }
+
+    #[tokio::test]
+    async fn test_select_rates_filter_sort_and_top() {
+        let options = RateListOptions {
+            targets: Some(vec![
+                "JPY".to_string(),
+                "GBP".to_string(),
+                "EUR".to_string(),
+            ]),
+            sort: RateSort::Rate,
+            top: Some(2),
+            ..RateListOptions::default()
+        };
+
+        let page = options.select_rates(&rate_tables());
+
+        assert_eq!(
+            codes(&page.rows),
+            vec!["EUR/GBP", "EUR/EUR", "USD/GBP", "USD/EUR"]
+        );
+        assert_eq!(page.rows[0].rate, Decimal::from_str("0.86").unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_select_rates_paging() {
+        let options = RateListOptions {
+            page: Some(2),
+            page_size: Some(3),
+            ..RateListOptions::default()
+        };
+
+        let page = options.select_rates(&rate_tables());
+
+        assert_eq!(codes(&page.rows), vec!["USD/EUR", "USD/GBP", "USD/JPY"]);
+        assert_eq!((page.page, page.pages), (2, 3));
+
+        let options = RateListOptions {
+            page: Some(4),
+            page_size: Some(3),
+            ..RateListOptions::default()
+        };
+
+        assert!(options.select_rates(&rate_tables()).rows.is_empty());
+
+        let options = RateListOptions {
+            page: Some(usize::MAX),
+            page_size: Some(2),
+            ..RateListOptions::default()
+        };
+        let page = options.select_rates(&rate_tables());
+
+        assert!(page.rows.is_empty());
+        assert_eq!((page.page, page.pages), (usize::MAX, 4));
+    }
+
+    #[tokio::test]
+    async fn test_list_rates_options_parse_args() {
+        let args = vec![
+            "currency_converter_cli".to_string(),
+            "-lr".to_string(),
+            "--base".to_string(),
+            "usd,eur".to_string(),
+            "--targets=GBP".to_string(),
+            "--sort".to_string(),
+            "rate".to_string(),
+            "--top".to_string(),
+            "5".to_string(),
+            "--page".to_string(),
+            "2".to_string(),
+        ];
+
+        let action = parse_cli_arguments(args).await;
+
+        assert_eq!(
+            action,
+            Action::ListCurrenciesWithRates {
+                options: RateListOptions {
+                    bases: Some(vec!["USD".to_string(), "EUR".to_string()]),
+                    targets: Some(vec!["GBP".to_string()]),
+                    sort: RateSort::Rate,
+                    top: Some(5),
+                    page: Some(2),
+                    page_size: None,
+                }
+            }
+        );
+        assert_eq!(
+            RateListOptions {
+                page: Some(2),
+                ..RateListOptions::default()
+            }
+            .get_page_size(),
+            Some(50)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_list_rates_invalid_options_parse_args() {
+        for (flag, value, message) in [
+            ("--top", "0", "Option --top must be a positive whole number"),
+            (
+                "--sort",
+                "name",
+                "name is not a valid sort order, use code or rate",
+            ),
+            ("--rounding", "floor", "Unknown option --rounding"),
+        ] {
+            let args = vec![
+                "currency_converter_cli".to_string(),
+                "list-currencies-with-rates".to_string(),
+                flag.to_string(),
+                value.to_string(),
+            ];
+
+            let action = parse_cli_arguments(args).await;
+
+            assert_eq!(
+                action,
+                Action::Error {
+                    message: message.to_string()
+                }
+            );
+        }
+    }
+}
@@ -0,0 +1,196 @@
+#[cfg(test)]
+mod tests {
+    use std::{collections::HashMap, str::FromStr};
+
+    use currency_converter_cli::{
+        cli::{
+            matrix::{
+                build_change_matrix, build_rate_matrix, format_change_cell, format_rate_cell,
+                get_matrix_currencies, MatrixView,
+            },
+            options::OutputFormat,
+            parse_cli_arguments, Action,
+        },
+        currency::{snapshot::RateSnapshots, Currency, RateTable},
+    };
+    use rust_decimal::Decimal;
+
+    fn decimal(value: &str) -> Decimal {
+        Decimal::from_str(value).unwrap()
+    }
+
+    fn usd_rate_table(eur: &str, gbp: &str) -> RateTable {
+        let rates = HashMap::from([
+            ("USD".to_string(), Decimal::ONE),
+            ("EUR".to_string(), decimal(eur)),
+            ("GBP".to_string(), decimal(gbp)),
+        ]);
+
+        RateTable::new(Currency::new_from_code("USD".to_string()), rates)
+    }
+
+    fn currencies() -> Vec<String> {
+        get_matrix_currencies("usd,EUR,GBP,usd")
+    }
+
+    fn view(rows: Vec<Vec<Option<String>>>) -> MatrixView {
+        MatrixView {
+            mode: "rates".to_string(),
+            inverted: false,
+            rate_timestamp: None,
+            compared_with: None,
+            currencies: vec!["USD".to_string(), "EUR".to_string()],
+            rows,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_matrix_currencies() {
+        assert_eq!(
+            currencies(),
+            vec!["USD".to_string(), "EUR".to_string(), "GBP".to_string()]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_build_rate_matrix() {
+        let rate_table = usd_rate_table("0.8", "0.64");
+
+        let matrix = build_rate_matrix(&currencies(), &rate_table, false).unwrap();
+
+        assert_eq!(
+            matrix,
+            vec![
+                vec![decimal("1"), decimal("0.8"), decimal("0.64")],
+                vec![decimal("1.25"), decimal("1"), decimal("0.8")],
+                vec![decimal("1.5625"), decimal("1.25"), decimal("1")],
+            ]
+        );
+
+        let inverted = build_rate_matrix(&currencies(), &rate_table, true).unwrap();
+
+        assert_eq!(inverted[0][1], decimal("1.25"));
+        assert_eq!(inverted[1][0], decimal("0.8"));
+
+        let missing =
+            build_rate_matrix(&["USD".to_string(), "CHF".to_string()], &rate_table, false);
+
+        assert_eq!(missing, Err("Exchange rate for CHF not found".to_string()));
+    }
+
+    #[tokio::test]
+    async fn test_build_change_matrix() {
+        let current =
+            build_rate_matrix(&currencies(), &usd_rate_table("0.88", "0.64"), false).unwrap();
+        let previous =
+            build_rate_matrix(&currencies(), &usd_rate_table("0.8", "0.64"), false).unwrap();
+
+        let change = build_change_matrix(&current, &previous);
+
+        assert_eq!(change[0][1], Some(decimal("10")));
+        assert_eq!(change[0][2], Some(decimal("0")));
+        assert_eq!(format_change_cell(change[0][1].unwrap()), "+10.00%");
+        assert_eq!(format_change_cell(change[1][0].unwrap()), "-9.09%");
+        assert_eq!(format_change_cell(change[2][2].unwrap()), "0.00%");
+    }
+
+    #[tokio::test]
+    async fn test_format_rate_cell() {
+        assert_eq!(
+            format_rate_cell(decimal("1.0847163466753443973315977872")),
+            "1.084716"
+        );
+        assert_eq!(format_rate_cell(decimal("151.3700")), "151.37");
+    }
+
+    #[tokio::test]
+    async fn test_matrix_outputs() {
+        let view = view(vec![
+            vec![Some("1".to_string()), Some("0.8".to_string())],
+            vec![Some("1.25".to_string()), None],
+        ]);
+
+        assert_eq!(
+            view.to_table().get_lines(),
+            vec![
+                "from \\ to   USD  EUR".to_string(),
+                "USD           1  0.8".to_string(),
+                "EUR        1.25    -".to_string(),
+            ]
+        );
+        assert_eq!(view.to_csv().unwrap(), ",USD,EUR\nUSD,1,0.8\nEUR,1.25,\n");
+        assert_eq!(
+            view.to_markdown(),
+            "| | USD | EUR |\n|---|---:|---:|\n| **USD** | 1 | 0.8 |\n| **EUR** | 1.25 | - |"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_rate_snapshots_record() {
+        let mut snapshots = RateSnapshots::default();
+
+        assert_eq!(snapshots.record(&usd_rate_table("0.8", "0.64")), None);
+        // unchanged rates keep the snapshot they were compared with
+        assert_eq!(snapshots.record(&usd_rate_table("0.8", "0.64")), None);
+
+        let earlier = snapshots.record(&usd_rate_table("0.88", "0.64")).unwrap();
+        assert_eq!(earlier.get_base(), "USD");
+        assert_eq!(earlier.get_rate_table().get_rate("EUR"), Ok(decimal("0.8")));
+
+        let earlier = snapshots.record(&usd_rate_table("0.88", "0.64")).unwrap();
+        assert_eq!(earlier.get_rate_table().get_rate("EUR"), Ok(decimal("0.8")));
+        assert_eq!(
+            snapshots
+                .get_latest()
+                .unwrap()
+                .get_rate_table()
+                .get_rate("EUR"),
+            Ok(decimal("0.88"))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_matrix_parse_args() {
+        let args = vec![
+            "currency_converter_cli".to_string(),
+            "matrix".to_string(),
+            "--invert".to_string(),
+            "usd,eur,gbp".to_string(),
+            "--change".to_string(),
+            "--format".to_string(),
+            "md".to_string(),
+        ];
+
+        let action = parse_cli_arguments(args).await;
+
+        assert_eq!(
+            action,
+            Action::Matrix {
+                currencies: vec!["USD".to_string(), "EUR".to_string(), "GBP".to_string()],
+                invert: true,
+                change: true,
+                format: OutputFormat::Markdown,
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_matrix_unknown_option_parse_args() {
+        let args = vec![
+            "currency_converter_cli".to_string(),
+            "matrix".to_string(),
+            "USD,EUR".to_string(),
+            "--spread".to_string(),
+            "1".to_string(),
+        ];
+
+        let action = parse_cli_arguments(args).await;
+
+        assert_eq!(
+            action,
+            Action::Error {
+                message: "Unknown option --spread".to_string()
+            }
+        );
+    }
+}
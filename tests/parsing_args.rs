@@ -1,6 +1,20 @@
 #[cfg(test)]
 mod tests {
-    use currency_converter_cli::cli::{parse_cli_arguments, Action};
+    use std::str::FromStr;
+
+    use currency_converter_cli::{
+        cli::{
+            direct_conversion::get_target_codes,
+            list_currencies_with_rates::RateListOptions,
+            natural_input::parse_natural_conversion,
+            options::{ConversionOptions, GlobalOptions},
+            parse_cli_arguments, parse_global_options,
+            table::Table,
+            Action,
+        },
+        conversion::{converter::FeeCurrency, rounding::RoundingMode},
+    };
+    use rust_decimal::Decimal;
 
     #[tokio::test]
     async fn test_direct_conversion_parse_args() {
@@ -18,7 +32,258 @@ mod tests {
             Action::DirectConversion {
                 source: "USD".to_string(),
                 target: "EUR".to_string(),
-                amount: "100".to_string()
+                amount: "100".to_string(),
+                options: ConversionOptions::default(),
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_direct_conversion_with_rounding_parse_args() {
+        let direct_conversion_args = vec![
+            "currency_converter_cli".to_string(),
+            "USD".to_string(),
+            "JPY".to_string(),
+            "100".to_string(),
+            "--rounding".to_string(),
+            "floor".to_string(),
+        ];
+
+        let action = parse_cli_arguments(direct_conversion_args).await;
+
+        assert_eq!(
+            action,
+            Action::DirectConversion {
+                source: "USD".to_string(),
+                target: "JPY".to_string(),
+                amount: "100".to_string(),
+                options: ConversionOptions {
+                    rounding: Some(RoundingMode::Floor),
+                    ..Default::default()
+                },
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_direct_conversion_with_fees_parse_args() {
+        let direct_conversion_args = vec![
+            "currency_converter_cli".to_string(),
+            "USD".to_string(),
+            "EUR".to_string(),
+            "100".to_string(),
+            "--spread".to_string(),
+            "0.5".to_string(),
+            "--fee=2".to_string(),
+            "--fee-currency".to_string(),
+            "target".to_string(),
+            "--min-fee".to_string(),
+            "3".to_string(),
+        ];
+
+        let action = parse_cli_arguments(direct_conversion_args).await;
+
+        assert_eq!(
+            action,
+            Action::DirectConversion {
+                source: "USD".to_string(),
+                target: "EUR".to_string(),
+                amount: "100".to_string(),
+                options: ConversionOptions {
+                    spread_percent: Some(Decimal::from_str("0.5").unwrap()),
+                    fixed_fee: Some(Decimal::from(2)),
+                    fixed_fee_currency: Some(FeeCurrency::Target),
+                    minimum_fee: Some(Decimal::from(3)),
+                    ..Default::default()
+                },
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_multi_target_conversion_parse_args() {
+        let direct_conversion_args = vec![
+            "currency_converter_cli".to_string(),
+            "USD".to_string(),
+            "EUR,GBP,JPY,PLN".to_string(),
+            "100".to_string(),
+        ];
+
+        let action = parse_cli_arguments(direct_conversion_args).await;
+
+        assert_eq!(
+            action,
+            Action::DirectConversion {
+                source: "USD".to_string(),
+                target: "EUR,GBP,JPY,PLN".to_string(),
+                amount: "100".to_string(),
+                options: ConversionOptions::default(),
+            }
+        );
+    }
+
+    async fn assert_natural_conversion(args: Vec<&str>, source: &str, target: &str, amount: &str) {
+        let mut natural_args = vec!["currency_converter_cli".to_string()];
+        natural_args.extend(args.iter().map(|arg| arg.to_string()));
+
+        let action = parse_cli_arguments(natural_args).await;
+
+        assert_eq!(
+            action,
+            Action::DirectConversion {
+                source: source.to_string(),
+                target: target.to_string(),
+                amount: amount.to_string(),
+                options: ConversionOptions::default(),
+            },
+            "{:?}",
+            args
+        );
+    }
+
+    #[tokio::test]
+    async fn test_natural_conversion_with_connector_parse_args() {
+        assert_natural_conversion(vec!["100", "usd", "to", "eur"], "USD", "EUR", "100").await;
+        assert_natural_conversion(vec!["100 USD in EUR"], "USD", "EUR", "100").await;
+    }
+
+    #[tokio::test]
+    async fn test_natural_conversion_with_attached_code_parse_args() {
+        assert_natural_conversion(vec!["100USD", "EUR"], "USD", "EUR", "100").await;
+        assert_natural_conversion(vec!["100MXN", "USD"], "MXN", "USD", "100").await;
+    }
+
+    #[tokio::test]
+    async fn test_natural_conversion_with_symbols_parse_args() {
+        assert_natural_conversion(vec!["$100", "in", "€"], "USD", "EUR", "100").await;
+        assert_natural_conversion(vec!["100", "zł", "to", "£"], "PLN", "GBP", "100").await;
+    }
+
+    #[tokio::test]
+    async fn test_natural_conversion_with_pair_parse_args() {
+        assert_natural_conversion(vec!["EUR/USD", "100"], "EUR", "USD", "100").await;
+    }
+
+    #[tokio::test]
+    async fn test_natural_conversion_with_suffix_parse_args() {
+        assert_natural_conversion(vec!["1.5k", "JPY", "to", "USD"], "JPY", "USD", "1500").await;
+        assert_natural_conversion(vec!["2m", "jpy", "usd"], "JPY", "USD", "2000000").await;
+    }
+
+    #[tokio::test]
+    async fn test_natural_conversion_with_non_ascii_suffix_parse_args() {
+        assert!(parse_natural_conversion("100\u{212a}EUR USD").is_err());
+        assert!(parse_natural_conversion("1\u{212a} USD EUR").is_err());
+        assert_natural_conversion(vec!["100KEUR", "USD"], "EUR", "USD", "100000").await;
+    }
+
+    #[tokio::test]
+    async fn test_expression_with_three_args_parse_args() {
+        let args = vec![
+            "currency_converter_cli".to_string(),
+            "100USD+250EUR".to_string(),
+            "in".to_string(),
+            "PLN".to_string(),
+        ];
+
+        assert_eq!(
+            parse_cli_arguments(args).await,
+            Action::Expression {
+                expression: "100USD+250EUR in PLN".to_string()
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_natural_conversion_invalid_input() {
+        assert!(parse_natural_conversion("to USD 100 EUR").is_err());
+        assert!(parse_natural_conversion("100 USD to to EUR").is_err());
+        assert!(parse_natural_conversion("100 US EUR").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_target_codes() {
+        let target_codes = get_target_codes("eur, GBP,,jpy");
+
+        assert_eq!(target_codes, vec!["EUR", "GBP", "JPY"]);
+    }
+
+    #[tokio::test]
+    async fn test_table_alignment() {
+        let mut table = Table::new(vec!["Target", "Amount"]);
+        table.add_row(vec!["EUR".to_string(), "92.19".to_string()]);
+        table.add_row(vec!["JPY".to_string(), "15123".to_string()]);
+
+        assert_eq!(
+            table.get_lines(),
+            vec!["Target  Amount", "EUR      92.19", "JPY      15123"]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_reverse_conversion_parse_args() {
+        let reverse_conversion_args = vec![
+            "currency_converter_cli".to_string(),
+            "USD".to_string(),
+            "EUR".to_string(),
+            "--receive".to_string(),
+            "500".to_string(),
+        ];
+
+        let action = parse_cli_arguments(reverse_conversion_args).await;
+
+        assert_eq!(
+            action,
+            Action::DirectConversion {
+                source: "USD".to_string(),
+                target: "EUR".to_string(),
+                amount: "500".to_string(),
+                options: ConversionOptions {
+                    reverse: true,
+                    ..Default::default()
+                },
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_reverse_conversion_with_two_amounts_parse_args() {
+        let reverse_conversion_args = vec![
+            "currency_converter_cli".to_string(),
+            "USD".to_string(),
+            "EUR".to_string(),
+            "100".to_string(),
+            "--receive".to_string(),
+            "500".to_string(),
+        ];
+
+        let action = parse_cli_arguments(reverse_conversion_args).await;
+
+        assert_eq!(
+            action,
+            Action::Error {
+                message: "Amount provided twice, use <source> <target> --receive <amount>"
+                    .to_string()
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_direct_conversion_with_invalid_rounding_parse_args() {
+        let direct_conversion_args = vec![
+            "currency_converter_cli".to_string(),
+            "USD".to_string(),
+            "JPY".to_string(),
+            "100".to_string(),
+            "--rounding=up".to_string(),
+        ];
+
+        let action = parse_cli_arguments(direct_conversion_args).await;
+
+        assert_eq!(
+            action,
+            Action::Error {
+                message: "up is not a valid rounding mode, use one of: half-up, half-even, floor, ceiling, truncate".to_string()
             }
         );
     }
@@ -49,7 +314,12 @@ mod tests {
 
         let action = parse_cli_arguments(supported_currencies_args).await;
 
-        assert_eq!(action, Action::ListCurrenciesWithRates);
+        assert_eq!(
+            action,
+            Action::ListCurrenciesWithRates {
+                options: RateListOptions::default()
+            }
+        );
     }
 
     #[tokio::test]
@@ -120,4 +390,94 @@ mod tests {
             }
         );
     }
+
+    fn cli_args(args: &[&str]) -> Vec<String> {
+        ["currency_converter_cli"]
+            .iter()
+            .chain(args)
+            .map(|arg| arg.to_string())
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_subcommands_parse_args() {
+        assert_eq!(
+            parse_cli_arguments(cli_args(&["convert", "USD", "EUR", "100"])).await,
+            parse_cli_arguments(cli_args(&["USD", "EUR", "100"])).await
+        );
+        assert_eq!(
+            parse_cli_arguments(cli_args(&["list"])).await,
+            Action::ListCurrencies
+        );
+        assert_eq!(
+            parse_cli_arguments(cli_args(&["rates", "--top", "5"])).await,
+            parse_cli_arguments(cli_args(&["-lr", "--top", "5"])).await
+        );
+        assert_eq!(
+            parse_cli_arguments(cli_args(&["cache", "update"])).await,
+            Action::UpdateCache
+        );
+        assert_eq!(
+            parse_cli_arguments(cli_args(&["config", "setup"])).await,
+            Action::Setup
+        );
+        assert_eq!(
+            parse_cli_arguments(cli_args(&["cache"])).await,
+            Action::Error {
+                message: "Use cache <update>".to_string()
+            }
+        );
+        assert_eq!(
+            parse_cli_arguments(cli_args(&["list", "--top", "5"])).await,
+            Action::Error {
+                message: "Unknown option --top".to_string()
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_global_options_parse_args() {
+        let args = cli_args(&["USD", "EUR", "100", "--offline", "--verbose", "--no-color"]);
+
+        assert_eq!(
+            parse_global_options(&args),
+            GlobalOptions {
+                no_color: true,
+                offline: true,
+                verbose: true,
+            }
+        );
+        assert_eq!(
+            parse_cli_arguments(args).await,
+            parse_cli_arguments(cli_args(&["USD", "EUR", "100"])).await
+        );
+        assert_eq!(
+            parse_cli_arguments(cli_args(&["list", "--format", "json"])).await,
+            Action::Error {
+                message: "Output format json is not supported here, use one of: table".to_string()
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_command_help_parse_args() {
+        assert_eq!(
+            parse_cli_arguments(cli_args(&["matrix", "--help"])).await,
+            Action::CommandHelp {
+                command: "matrix".to_string()
+            }
+        );
+        assert_eq!(
+            parse_cli_arguments(cli_args(&["help", "history", "export"])).await,
+            Action::CommandHelp {
+                command: "history export".to_string()
+            }
+        );
+        assert_eq!(
+            parse_cli_arguments(cli_args(&["update-cache", "--help"])).await,
+            Action::CommandHelp {
+                command: "cache update".to_string()
+            }
+        );
+    }
 }
@@ -0,0 +1,134 @@
+#[cfg(test)]
+mod tests {
+    use std::{collections::HashMap, str::FromStr};
+
+    use currency_converter_cli::{
+        cli::{
+            batch::read_csv_rows,
+            options::OutputFormat,
+            parse_cli_arguments,
+            portfolio::{value_holdings, HoldingValue},
+            Action,
+        },
+        conversion::rounding::RoundingMode,
+        currency::{Currency, RateTable},
+    };
+    use rust_decimal::Decimal;
+
+    fn usd_rate_table() -> RateTable {
+        let rates = HashMap::from([
+            ("USD".to_string(), Decimal::ONE),
+            ("EUR".to_string(), Decimal::from_str("0.8").unwrap()),
+            ("JPY".to_string(), Decimal::from_str("150").unwrap()),
+        ]);
+
+        RateTable::new(Currency::new_from_code("USD".to_string()), rates)
+    }
+
+    #[tokio::test]
+    async fn test_value_holdings() {
+        let input = "currency,amount,label\nUSD,\"1,000\",Checking\neur,800,Savings\nJPY,300000\n";
+        let rows = read_csv_rows(input.as_bytes(), "currency");
+
+        let valuation = value_holdings(&rows, &usd_rate_table(), RoundingMode::HalfEven);
+
+        assert_eq!(valuation.reporting_currency, "USD");
+        assert_eq!(valuation.total, "4000.00");
+        assert_eq!(
+            valuation.holdings[1],
+            HoldingValue {
+                line: 3,
+                label: Some("Savings".to_string()),
+                currency: "EUR".to_string(),
+                amount: "800".to_string(),
+                rate: Some("1.25".to_string()),
+                value: Some("1000.00".to_string()),
+                share_percent: Some("25.00".to_string()),
+                error: None,
+            }
+        );
+        assert_eq!(valuation.holdings[2].label, None);
+        assert_eq!(valuation.holdings[2].value, Some("2000.00".to_string()));
+        assert_eq!(
+            valuation.holdings[2].share_percent,
+            Some("50.00".to_string())
+        );
+    }
+
+    #[tokio::test]
+    async fn test_value_holdings_excludes_failed_holdings_from_total() {
+        let input = "USD,100\nGBP,100\nEUR,abc\nEUR\n";
+        let rows = read_csv_rows(input.as_bytes(), "currency");
+
+        let valuation = value_holdings(&rows, &usd_rate_table(), RoundingMode::HalfEven);
+        let errors: Vec<Option<String>> = valuation
+            .holdings
+            .iter()
+            .map(|holding| holding.error.clone())
+            .collect();
+
+        assert_eq!(valuation.total, "100.00");
+        assert_eq!(valuation.get_failed_holdings(), 3);
+        assert_eq!(
+            errors,
+            vec![
+                None,
+                Some("Exchange rate for GBP not found".to_string()),
+                Some("abc is not a valid number".to_string()),
+                Some(
+                    "Expected currency, amount and an optional label, found 1 columns".to_string()
+                ),
+            ]
+        );
+        assert_eq!(
+            valuation.holdings[0].share_percent,
+            Some("100.00".to_string())
+        );
+    }
+
+    #[tokio::test]
+    async fn test_value_parse_args() {
+        let args = vec![
+            "currency_converter_cli".to_string(),
+            "value".to_string(),
+            "holdings.csv".to_string(),
+            "usd".to_string(),
+            "--format=json".to_string(),
+            "--rounding".to_string(),
+            "floor".to_string(),
+        ];
+
+        let action = parse_cli_arguments(args).await;
+
+        assert_eq!(
+            action,
+            Action::Value {
+                input: "holdings.csv".to_string(),
+                currency: "USD".to_string(),
+                format: OutputFormat::Json,
+                rounding: Some(RoundingMode::Floor),
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_value_with_fees_parse_args() {
+        let args = vec![
+            "currency_converter_cli".to_string(),
+            "value".to_string(),
+            "holdings.csv".to_string(),
+            "USD".to_string(),
+            "--spread".to_string(),
+            "1".to_string(),
+        ];
+
+        let action = parse_cli_arguments(args).await;
+
+        assert_eq!(
+            action,
+            Action::Error {
+                message: "Only --rounding and --format are supported by value".to_string()
+            }
+        );
+    }
+}
@@ -0,0 +1,104 @@
+#[cfg(test)]
+mod tests {
+    use currency_converter_cli::{
+        cli::{parse_cli_arguments, Action},
+        currency::{
+            search::{rank_currencies, MatchReason},
+            Currency,
+        },
+    };
+
+    fn currencies() -> Vec<Currency> {
+        [
+            ("USD", "United States Dollar"),
+            ("AUD", "Australian Dollar"),
+            ("CAD", "Canadian Dollar"),
+            ("EUR", "Euro"),
+            ("GBP", "Pound Sterling"),
+            ("JPY", "Japanese Yen"),
+            ("PLN", "Polish Złoty"),
+            ("CHF", "Swiss Franc"),
+        ]
+        .iter()
+        .map(|(code, name)| Currency::new_from_code(code.to_string()).with_name(name.to_string()))
+        .collect()
+    }
+
+    fn ranked_codes(query: &str) -> Vec<String> {
+        rank_currencies(query, &currencies())
+            .into_iter()
+            .map(|currency_match| currency_match.currency.get_code().clone())
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_search_by_code_and_name() {
+        let matches = rank_currencies("eur", &currencies());
+        assert_eq!(matches[0].currency.get_code(), "EUR");
+        assert_eq!(matches[0].reason, MatchReason::Code);
+
+        assert_eq!(ranked_codes("dollar")[0], "USD");
+        assert_eq!(ranked_codes("dollar").len(), 3);
+        assert_eq!(ranked_codes("swiss"), vec!["CHF"]);
+    }
+
+    #[tokio::test]
+    async fn test_search_by_alias() {
+        let matches = rank_currencies("quid", &currencies());
+        assert_eq!(matches.len(), 1);
+        assert_eq!(matches[0].currency.get_code(), "GBP");
+        assert_eq!(matches[0].reason, MatchReason::Alias("quid".to_string()));
+
+        assert_eq!(ranked_codes("Yen"), vec!["JPY"]);
+    }
+
+    #[tokio::test]
+    async fn test_search_by_symbol() {
+        let matches = rank_currencies("$", &currencies());
+        assert_eq!(matches[0].currency.get_code(), "USD");
+        assert_eq!(matches[0].reason, MatchReason::Symbol("$".to_string()));
+
+        assert_eq!(ranked_codes("€")[0], "EUR");
+        assert_eq!(ranked_codes("zł")[0], "PLN");
+    }
+
+    #[tokio::test]
+    async fn test_search_by_country() {
+        let matches = rank_currencies("switzerland", &currencies());
+        assert_eq!(matches[0].currency.get_code(), "CHF");
+        assert_eq!(
+            matches[0].reason,
+            MatchReason::Country("Switzerland".to_string())
+        );
+
+        assert_eq!(ranked_codes("Germany"), vec!["EUR"]);
+        assert!(ranked_codes("atlantis").is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_search_parse_args() {
+        let args = vec!["currency_converter_cli", "search", "pound", "--limit", "3"]
+            .into_iter()
+            .map(String::from)
+            .collect();
+
+        let action = parse_cli_arguments(args).await;
+
+        assert_eq!(
+            action,
+            Action::Search {
+                query: "pound".to_string(),
+                limit: 3
+            }
+        );
+
+        let args = vec!["currency_converter_cli".to_string(), "search".to_string()];
+
+        assert_eq!(
+            parse_cli_arguments(args).await,
+            Action::Error {
+                message: "Use search <text>, example: search dollar".to_string()
+            }
+        );
+    }
+}
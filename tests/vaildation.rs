@@ -1,9 +1,20 @@
 #[cfg(test)]
 mod tests {
+    use std::str::FromStr;
+
     use currency_converter_cli::{
         currency::Currency,
-        validation::{validate, ValidationType},
+        validation::{
+            amount::{AmountFormat, AmountParser},
+            suggestions::{edit_distance, suggest_currencies},
+            validate, ValidationType,
+        },
     };
+    use rust_decimal::Decimal;
+
+    fn decimal(value: &str) -> Decimal {
+        Decimal::from_str(value).unwrap()
+    }
 
     #[tokio::test]
     async fn test_validation_currency_fail() {
@@ -13,12 +24,8 @@ mod tests {
             Currency::new_from_code("JPY".to_string()),
         ];
 
-        let validation = validate(
-            &"test".to_string(),
-            ValidationType::Currency,
-            Some(&accepted_currencies),
-        )
-        .await;
+        let validation =
+            validate("test", ValidationType::Currency, Some(&accepted_currencies)).await;
 
         assert_eq!(validation, Err("TEST is not a valid currency".to_string()));
     }
@@ -31,27 +38,235 @@ mod tests {
             Currency::new_from_code("JPY".to_string()),
         ];
 
-        let validation = validate(
-            &"USD".to_string(),
-            ValidationType::Currency,
-            Some(&accepted_currencies),
-        )
-        .await;
+        let validation =
+            validate("USD", ValidationType::Currency, Some(&accepted_currencies)).await;
 
         assert_eq!(validation, Ok(()));
     }
 
+    #[tokio::test]
+    async fn test_validation_currency_suggestions() {
+        let accepted_currencies = vec![
+            Currency::new_from_code("USD".to_string()),
+            Currency::new_from_code("EUR".to_string()),
+            Currency::new_from_code("JPY".to_string()),
+            Currency::new_from_code("GBP".to_string()),
+        ];
+
+        let validation =
+            validate("usf", ValidationType::Currency, Some(&accepted_currencies)).await;
+        assert_eq!(
+            validation,
+            Err("USF is not a valid currency, did you mean USD?".to_string())
+        );
+
+        let validation =
+            validate("Euro", ValidationType::Currency, Some(&accepted_currencies)).await;
+        assert_eq!(
+            validation,
+            Err("EURO is not a valid currency, did you mean EUR?".to_string())
+        );
+    }
+
+    #[tokio::test]
+    async fn test_currency_suggestions_are_ranked_and_limited() {
+        let currencies: Vec<Currency> = ["AUD", "CAD", "CHF", "USD", "XAF", "XAG", "XAU"]
+            .iter()
+            .map(|code| Currency::new_from_code(code.to_string()))
+            .collect();
+
+        assert_eq!(edit_distance("xad", "cad"), 1);
+        assert_eq!(edit_distance("japanese yen", "japanes yen"), 1);
+        assert_eq!(
+            suggest_currencies("XAD", &currencies),
+            vec!["CAD", "XAF", "XAG"]
+        );
+        assert_eq!(
+            suggest_currencies("Swiss Frank", &currencies),
+            vec!["CHF".to_string()]
+        );
+        assert!(suggest_currencies("E2121UR", &currencies).is_empty());
+    }
+
     #[tokio::test]
     async fn test_validation_amount_fail() {
-        let validation = validate(&"test".to_string(), ValidationType::Amount, None).await;
+        let validation = validate("test", ValidationType::Amount, None).await;
 
         assert_eq!(validation, Err("test is not a valid number".to_string()));
     }
 
     #[tokio::test]
     async fn test_validation_amount_success() {
-        let validation = validate(&"100".to_string(), ValidationType::Amount, None).await;
+        let validation = validate("100", ValidationType::Amount, None).await;
 
         assert_eq!(validation, Ok(()));
     }
+
+    #[tokio::test]
+    async fn test_validation_amount_rejects_non_finite_and_exponents() {
+        for input in ["NaN", "inf", "-inf", "1e308", ""] {
+            let validation = validate(input, ValidationType::Amount, None).await;
+
+            assert_eq!(validation, Err(format!("{} is not a valid number", input)));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_validation_amount_rejects_absurd_magnitudes() {
+        for input in [
+            "79228162514264337593543950335",
+            "1000000000000001",
+            "2000000bn",
+        ] {
+            let validation = validate(input, ValidationType::Amount, None).await;
+
+            assert_eq!(
+                validation,
+                Err(format!(
+                    "{} is too large, amounts are limited to 1000000000000000",
+                    input
+                ))
+            );
+        }
+
+        let validation = validate("1000000000000000", ValidationType::Amount, None).await;
+
+        assert_eq!(validation, Ok(()));
+    }
+
+    #[tokio::test]
+    async fn test_validation_amount_rejects_negative() {
+        let validation = validate("-5", ValidationType::Amount, None).await;
+
+        assert_eq!(validation, Err("-5 must not be negative".to_string()));
+    }
+
+    #[tokio::test]
+    async fn test_validation_amount_in_currency_precision() {
+        let validation = validate(
+            "10.505",
+            ValidationType::AmountIn(Currency::new_from_code("USD".to_string())),
+            None,
+        )
+        .await;
+
+        assert_eq!(
+            validation,
+            Err("10.505 has more decimal places than USD allows (2)".to_string())
+        );
+
+        let validation = validate(
+            "100.5",
+            ValidationType::AmountIn(Currency::new_from_code("JPY".to_string())),
+            None,
+        )
+        .await;
+
+        assert_eq!(
+            validation,
+            Err("100.5 has more decimal places than JPY allows (0)".to_string())
+        );
+
+        let validation = validate(
+            "10.50",
+            ValidationType::AmountIn(Currency::new_from_code("USD".to_string())),
+            None,
+        )
+        .await;
+
+        assert_eq!(validation, Ok(()));
+    }
+
+    #[test]
+    fn test_amount_parser_separators() {
+        let parser = AmountParser::default();
+
+        assert_eq!(parser.parse("1,000.50"), Ok(decimal("1000.50")));
+        assert_eq!(parser.parse("1 000,50"), Ok(decimal("1000.50")));
+        assert_eq!(parser.parse("1.000,50"), Ok(decimal("1000.50")));
+        assert_eq!(parser.parse("1'234'567.8"), Ok(decimal("1234567.8")));
+        assert_eq!(parser.parse("1,000"), Ok(decimal("1000")));
+        assert_eq!(parser.parse("12,5"), Ok(decimal("12.5")));
+        assert_eq!(parser.parse(".5"), Ok(decimal("0.5")));
+        assert_eq!(parser.parse("+100"), Ok(decimal("100")));
+    }
+
+    #[test]
+    fn test_amount_parser_rejects_malformed_grouping() {
+        let parser = AmountParser::default();
+
+        for input in ["1,00,0", "1.2.3", "10.", ",5,", "1,0000.5", "12abc"] {
+            assert_eq!(
+                parser.parse(input),
+                Err(format!("{} is not a valid number", input))
+            );
+        }
+    }
+
+    #[test]
+    fn test_amount_parser_non_ascii_suffix() {
+        let parser = AmountParser::default();
+
+        // the Kelvin sign lowercases to an ASCII k of a different length
+        assert_eq!(
+            parser.parse("1\u{212a}"),
+            Err("1\u{212a} is not a valid number".to_string())
+        );
+        assert_eq!(
+            parser.parse("2€m"),
+            Err("2€m is not a valid number".to_string())
+        );
+        assert_eq!(parser.parse("1K"), Ok(decimal("1000")));
+    }
+
+    #[test]
+    fn test_amount_parser_formats() {
+        let point = AmountParser::default().with_format(AmountFormat::DecimalPoint);
+        let comma = AmountParser::default().with_format(AmountFormat::DecimalComma);
+
+        assert_eq!(point.parse("1,000"), Ok(decimal("1000")));
+        assert_eq!(comma.parse("1,000"), Ok(decimal("1")));
+        assert_eq!(comma.parse("1.000"), Ok(decimal("1000")));
+        assert_eq!(
+            comma.parse("1,000.5"),
+            Err("1,000.5 is not a valid number".to_string())
+        );
+        assert_eq!(
+            AmountFormat::from_str("comma"),
+            Ok(AmountFormat::DecimalComma)
+        );
+        assert!(AmountFormat::from_str("dots").is_err());
+    }
+
+    #[test]
+    fn test_amount_parser_suffixes() {
+        let parser = AmountParser::default();
+
+        assert_eq!(parser.parse("1.5k"), Ok(decimal("1500")));
+        assert_eq!(parser.parse("2M"), Ok(decimal("2000000")));
+        assert_eq!(parser.parse("1,5 mn"), Ok(decimal("1500000")));
+        assert_eq!(parser.parse("3bn"), Ok(decimal("3000000000")));
+        assert_eq!(
+            parser.parse("k"),
+            Err("k is not a valid number".to_string())
+        );
+    }
+
+    #[test]
+    fn test_amount_parser_negative() {
+        let parser = AmountParser::default().allow_negative(true);
+
+        assert_eq!(parser.parse("-5"), Ok(decimal("-5")));
+        assert_eq!(parser.parse("−1,000.25"), Ok(decimal("-1000.25")));
+        assert_eq!(AmountParser::default().parse("-0"), Ok(decimal("0")));
+    }
+
+    #[test]
+    fn test_amount_parser_precision_after_suffix() {
+        let parser =
+            AmountParser::default().for_currency(&Currency::new_from_code("JPY".to_string()));
+
+        assert_eq!(parser.parse("1.5k"), Ok(decimal("1500")));
+        assert_eq!(parser.parse("1,000"), Ok(decimal("1000")));
+    }
 }